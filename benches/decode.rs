@@ -0,0 +1,103 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use vxl_iset::encoding::InstructionEncoding;
+use vxl_iset::instruction::Instruction;
+use vxl_iset::instruction_arguments::{Address, Condition, Immediate, Register};
+use vxl_iset::program::Program;
+
+const STEPS: usize = 10_000;
+
+/// A loop body mixing every operand kind, ending with a jump back to the start.
+fn program_bytes() -> Vec<u8> {
+    let body = [
+        Instruction::Ldi(Immediate::from(3u64), Register::R0),
+        Instruction::Addi(Register::R1, Register::R0, Register::R1),
+        Instruction::Copy(
+            Register::R5,
+            Register::R0,
+            Register::R6,
+            Register::R1,
+            Register::R7,
+        ),
+        Instruction::Setcc(Condition::LT, Register::R2),
+        Instruction::Csel(Condition::GEU, Register::R0, Register::R1, Register::R2),
+        Instruction::Nop,
+    ];
+    let mut bytes = Vec::new();
+
+    for _ in 0..16 {
+        for instruction in body {
+            bytes.extend(Into::<Vec<u8>>::into(instruction));
+        }
+    }
+
+    bytes.extend(Into::<Vec<u8>>::into(Instruction::Jmp(Address::from(0u64))));
+
+    bytes
+}
+
+/// Interprets the program by decoding the instruction at the program counter on every step.
+fn step_bytes(bytes: &[u8]) -> usize {
+    let mut offset = 0;
+    let mut decoded = 0;
+
+    for _ in 0..STEPS {
+        let (instruction, len) = Instruction::from_bytes(&bytes[offset..]).unwrap();
+
+        offset = match instruction {
+            Instruction::Jmp(address) => Into::<u64>::into(address) as usize,
+            _ => offset + len,
+        };
+        decoded += black_box(instruction).encoded_len();
+    }
+
+    decoded
+}
+
+/// Interprets the pre-decoded program, following the branch targets resolved while decoding.
+fn step_predecoded(program: &Program) -> usize {
+    let instructions = program.instructions();
+    let mut index = 0;
+    let mut decoded = 0;
+
+    for _ in 0..STEPS {
+        let instruction = instructions[index];
+
+        index = match instruction {
+            Instruction::Jmp(_) => program.target(index).unwrap(),
+            _ => index + 1,
+        };
+        decoded += black_box(instruction).encoded_len();
+    }
+
+    decoded
+}
+
+fn decode_benchmarks(c: &mut Criterion) {
+    let bytes = program_bytes();
+    let program = Program::predecode(&bytes).unwrap();
+
+    assert_eq!(step_bytes(&bytes), step_predecoded(&program));
+
+    c.bench_function("step bytes", |b| b.iter(|| step_bytes(black_box(&bytes))));
+    c.bench_function("step predecoded", |b| {
+        b.iter(|| step_predecoded(black_box(&program)))
+    });
+    c.bench_function("predecode", |b| {
+        b.iter(|| Program::predecode(black_box(&bytes)).unwrap())
+    });
+
+    let mut compact = Vec::new();
+
+    for instruction in program.instructions() {
+        let _ = InstructionEncoding::Compact.encode_to(instruction, &mut compact);
+    }
+
+    c.bench_function("predecode compact", |b| {
+        b.iter(|| {
+            Program::predecode_with(black_box(&compact), InstructionEncoding::Compact).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, decode_benchmarks);
+criterion_main!(benches);
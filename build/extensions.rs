@@ -0,0 +1,20 @@
+/// The extension tables, each with the cargo feature enabling it, the name of its `Extension`
+/// variant and its opcode range, in the order of the `Extension` variants. Shared by the build
+/// script and `Extension::opcode_range`.
+const EXTENSIONS: [(&str, &str, &str, u16, u16); 3] = [
+    (
+        "extensions/float_ext.csv",
+        "FLOAT_EXT",
+        "FloatExt",
+        0x80,
+        0x9f,
+    ),
+    (
+        "extensions/bitmanip.csv",
+        "BITMANIP",
+        "Bitmanip",
+        0xa0,
+        0xbf,
+    ),
+    ("extensions/system.csv", "SYSTEM", "System", 0xc0, 0xdf),
+];
@@ -1,78 +1,29 @@
-/*
-This script is very messy and it will be hard to expand in the future but for the moment it works and will do.
-*/
-mod config;
-mod constants;
-mod gen_execute_instruction;
-mod gen_instruction;
+use std::env;
 
-use std::fs::{File, OpenOptions};
-use std::path::Path;
+use vxl_iset_gen::Generator;
 
-use crate::config::{Instruction, InstructionDetails, Row};
-use constants::*;
-use gen_execute_instruction::generate_execute_trait;
-use gen_instruction::generate_instruction_file;
+const INSTRUCTION_SET_PATH: &str = "base_instruction_set.csv";
+
+include!("extensions.rs");
 
 fn main() {
     println!("cargo:rerun-if-changed={}", INSTRUCTION_SET_PATH);
 
-    let instructions = load_method_details();
-
-    generate_instruction_file(&instructions);
-    generate_execute_trait(&instructions);
-}
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is not set.");
+    let mut generator = Generator::from_path(INSTRUCTION_SET_PATH);
 
-fn open_file_writing(path: &str) -> File {
-    return match OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(path)
-    {
-        Ok(f) => f,
-        Err(e) => {
-            panic!(
-                "Failed to open the file {}. Error: {}",
-                EXECUTE_OUTPUT_PATH, e
-            );
-        }
-    };
-}
+    for (path, feature, variant, first, last) in EXTENSIONS {
+        println!("cargo:rerun-if-changed={}", path);
 
-fn open_file_reading(path: &str) -> File {
-    return match OpenOptions::new().read(true).open(path) {
-        Ok(f) => f,
-        Err(e) => {
-            panic!(
-                "Failed to open the file {}. Error: {}",
-                EXECUTE_OUTPUT_PATH, e
-            );
+        if env::var_os(format!("CARGO_FEATURE_{}", feature)).is_some() {
+            generator = generator
+                .and_then(|generator| generator.with_extension(path, variant, first..=last));
         }
-    };
-}
-
-fn load_method_details() -> InstructionDetails {
-    if !Path::new(INSTRUCTION_SET_PATH).exists() {
-        panic!("No instruction set file found at {}", INSTRUCTION_SET_PATH);
     }
 
-    let file = open_file_reading(INSTRUCTION_SET_PATH);
-    let mut csv_reader = csv::Reader::from_reader(file);
-    let mut rows = Vec::new();
-
-    for record in csv_reader.records() {
-        if let Ok(record) = record {
-            match record.deserialize::<Row>(None) {
-                Ok(r) => rows.push(r),
-                Err(e) => {
-                    panic!("Could not deserialize row. Error: {}", e);
-                }
-            }
-        } else {
-            panic!("Could not access row.");
-        }
+    if let Err(e) =
+        generator.and_then(|generator| generator.with_support_path("crate").write_to(out_dir))
+    {
+        panic!("{}", e);
     }
-
-    return rows.into_iter().map(|row| Instruction::from(row)).collect();
 }
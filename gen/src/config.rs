@@ -0,0 +1,533 @@
+use crate::constants::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub type InstructionDetails = Vec<Instruction>;
+
+#[derive(Deserialize, Debug, PartialEq, Eq, Hash)]
+pub struct Row {
+    pub binary: String,
+    pub decimal: u16,
+    pub hex: String,
+    pub opcode: String,
+    pub description: String,
+    pub immediates: u8,
+    pub registers: u8,
+    pub addresses: u8,
+    pub conditions: u8,
+    pub order: String,
+    pub reads: String,
+    pub writes: String,
+    pub properties: String,
+    pub immediate_types: String,
+    pub address_kinds: String,
+    pub displacements: u8,
+    pub displacement_widths: String,
+}
+
+/// How the values of an operand kind are laid out once an instruction is encoded.
+#[derive(Debug)]
+pub enum Encoding {
+    /// Every value takes up `BYTES` bytes of its own.
+    Bytes,
+    /// Every value takes up 4 bits. Consecutive nibble operands are packed two to a byte, high
+    /// nibble first, with the last byte padded with zeros.
+    Nibble,
+    /// Every value takes up the number of bytes declared for it in a column of the instruction set,
+    /// at most `BYTES`. Encoding a value that does not fit in its declared width fails with
+    /// `EncodeError::DisplacementOutOfRange`, only displacements have declared widths.
+    Declared(&'static WidthColumn),
+}
+
+/// A column declaring the width in bits of every operand of a kind.
+#[derive(Debug)]
+pub struct WidthColumn {
+    /// The name of the column holding the widths of each operand in order.
+    pub column: &'static str,
+    /// The widths in bits the operand type can decode from.
+    pub widths: &'static [usize],
+    /// Reads the column from a row of the instruction set.
+    pub row_list: fn(&Row) -> &str,
+}
+
+/// Describes a kind of operand an instruction can take. Supporting a new kind of operand only
+/// requires a type implementing `InstructionArgument`, a column in the instruction set and an entry
+/// in `OPERAND_KINDS`.
+#[derive(Debug)]
+pub struct OperandKind {
+    /// The name of the rust type for this operand.
+    pub type_name: &'static str,
+    /// The character used in the friendly ordering column and as the prefix of generated names.
+    pub symbol: char,
+    /// The value returned by `get_type_for_index`, this should match the `OperandKind` enum.
+    pub type_code: u8,
+    /// The name of the generated method returning the number of operands of this kind.
+    pub count_method: &'static str,
+    /// The name of the argument holding operands of this kind in the generated `new` method.
+    pub vector_name: &'static str,
+    pub encoding: Encoding,
+    /// Reads the number of operands of this kind from a row of the instruction set.
+    pub row_count: fn(&Row) -> u8,
+    /// The tag every operand of this kind is declared with, if any.
+    pub tag: Option<&'static OperandTag>,
+    /// The name of the `CompactForm` variant operands of this kind are written with in the compact
+    /// encoding, given the tag variant of the operand. None if the kind is written as in the fixed
+    /// encoding, its values must then convert to and from `u64`.
+    pub compact_form: Option<fn(Option<&str>) -> &'static str>,
+}
+
+/// A tag declared in the instruction set for every operand of a kind. Tags are not encoded, decoding
+/// restores them from the instruction set.
+#[derive(Debug)]
+pub struct OperandTag {
+    /// The name of the column holding the tags of each operand in order.
+    pub column: &'static str,
+    /// The name of the rust enum of the tag.
+    pub type_name: &'static str,
+    /// The name of the method of the operand type returning it with a tag.
+    pub method: &'static str,
+    /// The names used in the column, paired with the name of their enum variant.
+    pub values: &'static [(&'static str, &'static str)],
+    /// Reads the column from a row of the instruction set.
+    pub row_list: fn(&Row) -> &str,
+}
+
+/// The types immediates are declared with.
+pub static IMMEDIATE_TYPE_TAG: OperandTag = OperandTag {
+    column: "Immediate Types",
+    type_name: IMMEDIATE_TYPE_TYPE_NAME,
+    method: "with_type",
+    values: &[
+        ("unsigned", "UnsignedInteger"),
+        ("signed", "SignedInteger"),
+        ("float", "Float"),
+    ],
+    row_list: |row| &row.immediate_types,
+};
+
+/// What addresses are declared to refer to.
+pub static ADDRESS_KIND_TAG: OperandTag = OperandTag {
+    column: "Address Kinds",
+    type_name: ADDRESS_KIND_TYPE_NAME,
+    method: "with_kind",
+    values: &[("code", "Code"), ("heap", "Heap")],
+    row_list: |row| &row.address_kinds,
+};
+
+/// The widths displacements are encoded in.
+pub static DISPLACEMENT_WIDTHS: WidthColumn = WidthColumn {
+    column: "Displacement Widths",
+    widths: &[8, 16, 32],
+    row_list: |row| &row.displacement_widths,
+};
+
+/// The symbol of register operands, also used to refer to them in the reads and writes columns.
+pub const REGISTER_SYMBOL: char = 'r';
+
+/// Every supported operand kind, in the order the fields appear in each variant and in the encoding.
+pub static OPERAND_KINDS: [OperandKind; 5] = [
+    OperandKind {
+        type_name: IMMEDIATE_TYPE_NAME,
+        symbol: 'i',
+        type_code: 1,
+        count_method: OPCODE_IMMEDIATE_COUNT_METHOD_HEADER,
+        vector_name: "immediates",
+        encoding: Encoding::Bytes,
+        row_count: |row| row.immediates,
+        tag: Some(&IMMEDIATE_TYPE_TAG),
+        compact_form: Some(|tag| match tag {
+            Some("SignedInteger") => "Signed",
+            Some("Float") => "Fixed",
+            _ => "Unsigned",
+        }),
+    },
+    OperandKind {
+        type_name: CONDITION_TYPE_NAME,
+        symbol: 'c',
+        type_code: 3,
+        count_method: OPCODE_CONDITION_COUNT_METHOD_HEADER,
+        vector_name: "conditions",
+        encoding: Encoding::Nibble,
+        row_count: |row| row.conditions,
+        tag: None,
+        compact_form: None,
+    },
+    OperandKind {
+        type_name: REGISTER_TYPE_NAME,
+        symbol: REGISTER_SYMBOL,
+        type_code: 0,
+        count_method: OPCODE_REGISTER_COUNT_METHOD_HEADER,
+        vector_name: "registers",
+        encoding: Encoding::Nibble,
+        row_count: |row| row.registers,
+        tag: None,
+        compact_form: None,
+    },
+    OperandKind {
+        type_name: ADDRESS_TYPE_NAME,
+        symbol: 'a',
+        type_code: 2,
+        count_method: OPCODE_ADDRESS_COUNT_METHOD_HEADER,
+        vector_name: "addresses",
+        encoding: Encoding::Bytes,
+        row_count: |row| row.addresses,
+        tag: Some(&ADDRESS_KIND_TAG),
+        compact_form: Some(|_| "Unsigned"),
+    },
+    OperandKind {
+        type_name: DISPLACEMENT_TYPE_NAME,
+        symbol: 'd',
+        type_code: 4,
+        count_method: OPCODE_DISPLACEMENT_COUNT_METHOD_HEADER,
+        vector_name: "displacements",
+        encoding: Encoding::Declared(&DISPLACEMENT_WIDTHS),
+        row_count: |row| row.displacements,
+        tag: None,
+        compact_form: None,
+    },
+];
+
+impl OperandKind {
+    /// Returns the operand kind used for a character of the friendly ordering.
+    pub fn for_symbol(symbol: char) -> Option<&'static OperandKind> {
+        return OPERAND_KINDS.iter().find(|kind| kind.symbol == symbol);
+    }
+
+    /// The name used for the operand at an index amongst operands of this kind, e.g. r, r1, r2.
+    pub fn field_name(&self, index: usize) -> String {
+        if index == 0 {
+            return self.symbol.to_string();
+        } else {
+            return format!("{}{}", self.symbol, index);
+        }
+    }
+
+    /// The one based name used for the operand at an index amongst operands of this kind, e.g. r1, r2.
+    pub fn numbered_name(&self, index: usize) -> String {
+        return format!("{}{}", self.symbol, index + 1);
+    }
+}
+
+/// The byte prefixing opcodes that do not fit in a single byte, this should match `Opcode::ESCAPE`.
+pub const ESCAPE_BYTE: u8 = 0xff;
+/// The first opcode encoded with the escape prefix, this should match `Opcode::FIRST_ESCAPED`.
+pub const FIRST_ESCAPED_OPCODE: u16 = 0xff00;
+
+/// Registers that instructions can read or write without them being an operand, paired with the
+/// name of their `Register` variant.
+pub static IMPLICIT_REGISTERS: [(&'static str, &'static str); 4] = [
+    ("rsp", "RSP"),
+    ("rfp", "RFP"),
+    ("rou", "ROU"),
+    ("rfl", "RFL"),
+];
+
+/// The `Register` variant of the flags register.
+pub const FLAGS_REGISTER: &'static str = "RFL";
+
+/// A register read or written by an instruction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegisterUse {
+    /// The register operand at an index amongst the register operands.
+    Operand(usize),
+    /// A register that is not an operand, holding the name of its `Register` variant.
+    Implicit(&'static str),
+}
+
+impl RegisterUse {
+    /// Parses a space separated list of registers from the reads or writes columns. Operands are
+    /// referred to by their one based name, e.g. r1, and other registers by their assembly name.
+    pub fn parse_list(list: &str, register_count: u8) -> Result<Vec<RegisterUse>, String> {
+        return list
+            .split_whitespace()
+            .map(|name| {
+                if let Some((_, variant)) = IMPLICIT_REGISTERS.iter().find(|(n, _)| *n == name) {
+                    return Ok(RegisterUse::Implicit(variant));
+                }
+
+                return match name.strip_prefix(REGISTER_SYMBOL).map(str::parse::<usize>) {
+                    Some(Ok(index)) if index >= 1 && index <= register_count as usize => {
+                        Ok(RegisterUse::Operand(index - 1))
+                    }
+                    _ => Err(format!("Unknown register {}", name)),
+                };
+            })
+            .collect();
+    }
+}
+
+impl WidthColumn {
+    /// Parses the space separated widths of the operands of a row, returning the number of bytes
+    /// of each operand in order. Every operand must be given a width.
+    pub fn parse_list(&self, row: &Row, operand_count: u8) -> Result<Vec<usize>, String> {
+        let widths = (self.row_list)(row)
+            .split_whitespace()
+            .map(|width| match width.parse::<usize>() {
+                Ok(bits) if self.widths.contains(&bits) => Ok(bits / 8),
+                _ => Err(format!(
+                    "Invalid width {} in the {} column, expected one of {:?}",
+                    width, self.column, self.widths
+                )),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if widths.len() != operand_count as usize {
+            return Err(format!(
+                "{} widths in the {} column but the instruction has {} operands of that kind",
+                widths.len(),
+                self.column,
+                operand_count
+            ));
+        }
+
+        return Ok(widths);
+    }
+}
+
+impl OperandTag {
+    /// Parses the space separated tags of the operands of a row, returning the enum variant of each
+    /// operand in order. Every operand must be given a tag.
+    pub fn parse_list(&self, row: &Row, operand_count: u8) -> Result<Vec<&'static str>, String> {
+        let tags = (self.row_list)(row)
+            .split_whitespace()
+            .map(|name| {
+                self.values
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, variant)| *variant)
+                    .ok_or_else(|| format!("Unknown tag {} in the {} column", name, self.column))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if tags.len() != operand_count as usize {
+            return Err(format!(
+                "{} tags in the {} column but the instruction has {} operands of that kind",
+                tags.len(),
+                self.column,
+                operand_count
+            ));
+        }
+
+        return Ok(tags);
+    }
+}
+
+/// Describes a property of an instruction from the properties column.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Property {
+    /// The name used in the properties column.
+    pub name: &'static str,
+    /// The name of the generated method testing for the property.
+    pub method: &'static str,
+    /// The documentation of the generated method.
+    pub doc: &'static str,
+}
+
+/// Every property an instruction can be declared with.
+pub static PROPERTIES: [Property; 4] = [
+    Property {
+        name: "branch",
+        method: "is_branch",
+        doc: "True if the instruction can continue execution somewhere other than the following instruction.",
+    },
+    Property {
+        name: "terminator",
+        method: "is_terminator",
+        doc: "True if the instruction never continues execution at the following instruction.",
+    },
+    Property {
+        name: "trap",
+        method: "may_trap",
+        doc: "True if the instruction can raise a fault or trap to the host.",
+    },
+    Property {
+        name: "heap",
+        method: "touches_heap",
+        doc: "True if the instruction can allocate, free, read or write heap memory.",
+    },
+];
+
+impl Property {
+    /// Parses a space separated list of properties from the properties column.
+    pub fn parse_list(list: &str) -> Result<Vec<Property>, String> {
+        return list
+            .split_whitespace()
+            .map(|name| {
+                PROPERTIES
+                    .iter()
+                    .find(|property| property.name == name)
+                    .copied()
+                    .ok_or_else(|| format!("Unknown property {}", name))
+            })
+            .collect();
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub short_name: String, // opcode
+    pub description: String,
+    pub fields: Vec<&'static OperandKind>,
+    pub name: String,
+    pub opcode_num: u16,
+    pub argument_order: Vec<&'static OperandKind>,
+    pub reads: Vec<RegisterUse>,
+    pub writes: Vec<RegisterUse>,
+    pub properties: Vec<Property>,
+    /// The tag variant of each operand of the kinds with tags, keyed by the symbol of the kind.
+    pub tags: HashMap<char, Vec<&'static str>>,
+    /// The number of bytes of each operand of the kinds with declared widths, keyed by the symbol
+    /// of the kind.
+    pub widths: HashMap<char, Vec<usize>>,
+    /// The name of the `Extension` variant of the table the instruction was declared in, None for
+    /// the base table.
+    pub extension: Option<String>,
+    /// The line of the row declaring the instruction in its table.
+    pub line: u64,
+}
+
+impl Instruction {
+    pub fn new(
+        short_name: String,
+        description: String,
+        fields: Vec<&'static OperandKind>,
+        name: String,
+        opcode_num: u16,
+        argument_order: &str,
+    ) -> Self {
+        return Self {
+            short_name,
+            description,
+            fields,
+            name,
+            opcode_num,
+            argument_order: argument_order
+                .chars()
+                .map(|symbol| {
+                    OperandKind::for_symbol(symbol)
+                        .unwrap_or_else(|| panic!("Unknown order symbol {}", symbol))
+                })
+                .collect(),
+            reads: Vec::new(),
+            writes: Vec::new(),
+            properties: Vec::new(),
+            tags: HashMap::new(),
+            widths: HashMap::new(),
+            extension: None,
+            line: 0,
+        };
+    }
+
+    /// Pairs each field with its index amongst the fields of the same kind.
+    pub fn indexed_fields(&self) -> Vec<(&'static OperandKind, usize)> {
+        return index_by_kind(&self.fields);
+    }
+
+    /// Pairs each operand of the friendly ordering with the index of the field it refers to amongst
+    /// the fields of the same kind.
+    pub fn indexed_order(&self) -> Vec<(&'static OperandKind, usize)> {
+        return index_by_kind(&self.argument_order);
+    }
+
+    /// True if the instruction writes to the flags register.
+    pub fn affects_flags(&self) -> bool {
+        return self.writes.contains(&RegisterUse::Implicit(FLAGS_REGISTER));
+    }
+
+    /// The bytes the opcode is encoded as, escaped opcodes are prefixed with `ESCAPE_BYTE`.
+    pub fn opcode_bytes(&self) -> Vec<u8> {
+        if self.opcode_num >= FIRST_ESCAPED_OPCODE {
+            return vec![ESCAPE_BYTE, self.opcode_num as u8];
+        } else {
+            return vec![self.opcode_num as u8];
+        }
+    }
+
+    /// The number of bytes the operand at an index amongst the operands of a kind with declared
+    /// widths is encoded in.
+    pub fn width(&self, kind: &OperandKind, index: usize) -> usize {
+        return self.widths[&kind.symbol][index];
+    }
+
+    /// The name of the `CompactForm` variant the operand at an index amongst the operands of a kind
+    /// is written with in the compact encoding, None if it is written as in the fixed encoding.
+    pub fn compact_form(&self, kind: &OperandKind, index: usize) -> Option<&'static str> {
+        let tag = self.tags.get(&kind.symbol).map(|tags| tags[index]);
+
+        return kind.compact_form.map(|form| form(tag));
+    }
+
+    /// The number of fields of a specific kind.
+    pub fn count(&self, kind: &OperandKind) -> usize {
+        return self
+            .fields
+            .iter()
+            .filter(|field| field.symbol == kind.symbol)
+            .count();
+    }
+}
+
+fn index_by_kind(kinds: &[&'static OperandKind]) -> Vec<(&'static OperandKind, usize)> {
+    let mut counts = HashMap::new();
+
+    return kinds
+        .iter()
+        .map(|kind| {
+            let count = counts.entry(kind.symbol).or_insert(0);
+            *count += 1;
+
+            (*kind, *count - 1)
+        })
+        .collect();
+}
+
+impl From<Row> for Instruction {
+    fn from(row: Row) -> Self {
+        let mut opcode = row.opcode.clone();
+        let variant_name = format!("{}{}", opcode.remove(0).to_uppercase(), opcode);
+        let mut fields = Vec::new();
+
+        for kind in OPERAND_KINDS.iter() {
+            fields.extend((0..(kind.row_count)(&row)).map(|_| kind));
+        }
+
+        let reads = RegisterUse::parse_list(&row.reads, row.registers).unwrap();
+        let writes = RegisterUse::parse_list(&row.writes, row.registers).unwrap();
+        let properties = Property::parse_list(&row.properties).unwrap();
+        let tags = OPERAND_KINDS
+            .iter()
+            .filter_map(|kind| {
+                let tags = kind.tag?.parse_list(&row, (kind.row_count)(&row)).unwrap();
+
+                Some((kind.symbol, tags))
+            })
+            .collect();
+        let widths = OPERAND_KINDS
+            .iter()
+            .filter_map(|kind| match kind.encoding {
+                Encoding::Declared(widths) => Some((
+                    kind.symbol,
+                    widths.parse_list(&row, (kind.row_count)(&row)).unwrap(),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        let mut instruction = Self::new(
+            row.opcode,
+            row.description,
+            fields,
+            variant_name,
+            row.decimal,
+            &row.order,
+        );
+
+        instruction.reads = reads;
+        instruction.writes = writes;
+        instruction.properties = properties;
+        instruction.tags = tags;
+        instruction.widths = widths;
+
+        return instruction;
+    }
+}
@@ -0,0 +1,35 @@
+pub const BASE_IMPORTS: [&'static str; 5] = ["Vec", "vec", "format", "String", "ToString"];
+pub const PRIVATE_MODULE_NAME: &'static str = "__private";
+pub const OPCODE_OUTPUT_FILE: &'static str = "instruction.rs";
+pub const EXECUTE_OUTPUT_FILE: &'static str = "execute_instruction.rs";
+pub const OPCODE_ENUM_NAME: &'static str = "Instruction";
+pub const REGISTER_TYPE_NAME: &'static str = "Register";
+pub const ADDRESS_TYPE_NAME: &'static str = "Address";
+pub const ADDRESS_KIND_TYPE_NAME: &'static str = "AddressKind";
+pub const IMMEDIATE_TYPE_NAME: &'static str = "Immediate";
+pub const IMMEDIATE_TYPE_TYPE_NAME: &'static str = "ImmediateType";
+pub const DISPLACEMENT_TYPE_NAME: &'static str = "Displacement";
+pub const CONDITION_TYPE_NAME: &'static str = "Condition";
+pub const INSTRUCTION_ARGUMENT_TRAIT_NAME: &'static str = "InstructionArgument";
+pub const REGISTER_SET_TYPE_NAME: &'static str = "RegisterSet";
+pub const OPERAND_KIND_TYPE_NAME: &'static str = "OperandKind";
+pub const OPERAND_TYPE_NAME: &'static str = "Operand";
+pub const INSTRUCTION_INFO_TYPE_NAME: &'static str = "InstructionInfo";
+pub const OPCODE_TYPE_NAME: &'static str = "Opcode";
+pub const OPCODE_TYPE_DERIVE_TRAITS: &'static str =
+    "#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]";
+pub const UNKNOWN_OPCODE_TYPE_NAME: &'static str = "UnknownOpcode";
+pub const BYTE_SINK_TRAIT_NAME: &'static str = "ByteSink";
+pub const BUFFER_TOO_SMALL_TYPE_NAME: &'static str = "BufferTooSmall";
+pub const ENCODE_ERROR_TYPE_NAME: &'static str = "EncodeError";
+pub const COMPACT_FORM_TYPE_NAME: &'static str = "CompactForm";
+pub const EXTENSION_TYPE_NAME: &'static str = "Extension";
+pub const INSTRUCTIONS_TABLE_NAME: &'static str = "INSTRUCTIONS";
+pub const VISITOR_TRAIT_NAME: &'static str = "InstructionVisitor";
+pub const EXECUTE_TRAIT_NAME: &'static str = "ExecuteInstruction";
+pub const OPCODE_DERIVE_TRAITS: &'static str = "#[derive(Debug, Clone, Copy, PartialEq)]";
+pub const OPCODE_REGISTER_COUNT_METHOD_HEADER: &'static str = "register_count";
+pub const OPCODE_ADDRESS_COUNT_METHOD_HEADER: &'static str = "address_count";
+pub const OPCODE_IMMEDIATE_COUNT_METHOD_HEADER: &'static str = "immediate_count";
+pub const OPCODE_CONDITION_COUNT_METHOD_HEADER: &'static str = "condition_count";
+pub const OPCODE_DISPLACEMENT_COUNT_METHOD_HEADER: &'static str = "displacement_count";
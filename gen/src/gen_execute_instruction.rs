@@ -0,0 +1,100 @@
+use crate::config::*;
+use crate::constants::*;
+use itertools::Itertools;
+use rmod_gen::rust_component::Visibility;
+use rmod_gen::{RustFile, RustMethod, RustText, RustTrait};
+
+/// Generates the execute instruction module, it expects the instruction module to be a sibling.
+/// Items from the support crate are imported from `support_path`.
+pub fn generate_execute_trait(instructions: &InstructionDetails, support_path: &str) -> String {
+    let mut file = RustFile::new()
+        .with_import(&format!(
+            "use {}::instruction_arguments::{{{}}}",
+            support_path,
+            OPERAND_KINDS.iter().map(|kind| kind.type_name).join(", ")
+        ))
+        .with_import(&format!("use super::instruction::{}", OPCODE_ENUM_NAME))
+        .with_import(&format!(
+            "use {}::syscall_handler::SyscallHandler",
+            support_path
+        ))
+        .with_top_string("/*\nNOTE: THIS FILE IS AUTOGENERATED.\n*/\n");
+
+    let mut execute_trait = RustTrait::new(EXECUTE_TRAIT_NAME)
+        .with_component(RustText::new(&format!("type Machine: {};", EXECUTE_TRAIT_NAME)).into())
+        .with_component(RustText::new("type Output;\n").into())
+        .with_visibility(Visibility::Public);
+
+    let general_execute_method = RustMethod::new("execute_instruction")
+        .with_template("S: SyscallHandler<Self::Machine>")
+        .with_argument("&mut self")
+        .with_argument(&format!("instruction: {}", OPCODE_ENUM_NAME))
+        .with_argument("handler: &mut S")
+        .with_return_type("Self::Output")
+        .with_body(&format!(
+            "return match instruction {{\n{}}};\n",
+            instructions
+                .iter()
+                .map(|instruction| {
+                    let field_string = instruction
+                        .indexed_fields()
+                        .into_iter()
+                        .map(|(kind, index)| kind.field_name(index))
+                        .join(", ");
+
+                    if !field_string.is_empty() {
+                        if instruction.short_name == "syscall" {
+                            format!(
+                                "\t{}::{}({}) => self.execute_{}(handler, {}),\n",
+                                OPCODE_ENUM_NAME,
+                                &instruction.name,
+                                &field_string,
+                                &instruction.short_name,
+                                field_string,
+                            )
+                        } else {
+                            format!(
+                                "\t{}::{}({}) => self.execute_{}({}),\n",
+                                OPCODE_ENUM_NAME,
+                                &instruction.name,
+                                &field_string,
+                                &instruction.short_name,
+                                field_string,
+                            )
+                        }
+                    } else {
+                        format!(
+                            "\t{}::{} => self.execute_{}(),\n",
+                            OPCODE_ENUM_NAME, &instruction.name, &instruction.short_name
+                        )
+                    }
+                })
+                .collect::<String>()
+        ));
+
+    execute_trait.push_component(general_execute_method.into());
+
+    for instruction in instructions {
+        let mut method = RustMethod::new(&format!("execute_{}", instruction.short_name))
+            .with_argument("&mut self")
+            .with_return_type("Self::Output")
+            .without_body();
+
+        if instruction.short_name == "syscall" {
+            method.push_template("S: SyscallHandler<Self::Machine>");
+            method.push_argument("handler: &mut S");
+        }
+
+        for (kind, index) in instruction.indexed_fields() {
+            method.push_argument(&format!("{}: {}", kind.field_name(index), kind.type_name));
+        }
+
+        execute_trait
+            .push_component(RustText::new(&format!("/// {}", instruction.description)).into());
+        execute_trait.push_component(method.into());
+    }
+
+    file.push_component(execute_trait.into());
+
+    return file.to_rust_code();
+}
@@ -0,0 +1,1147 @@
+use crate::config::*;
+use crate::constants::*;
+use crate::gen_opcode::push_opcode_components;
+use crate::gen_visitor::generate_visitor_trait;
+use itertools::Itertools;
+use rmod_gen::rust_component::Visibility;
+use rmod_gen::{EnumVariant, RustEnum, RustFile, RustImplementation, RustMethod, RustText};
+
+/// Generates the instruction module, items from the support crate are imported from `support_path`.
+pub fn generate_instruction_file(
+    instruction_details: &InstructionDetails,
+    support_path: &str,
+) -> String {
+    let mut rust_file = RustFile::new()
+        .with_top_string("/*\nNOTE: THIS FILE IS AUTOGENERATED.\n*/\n")
+        .with_import(&format!(
+            "use {}::instruction_arguments::{{{}, {}, {}, {}, {}, {}}}",
+            support_path,
+            OPERAND_KINDS.iter().map(|kind| kind.type_name).join(", "),
+            OPERAND_KINDS
+                .iter()
+                .filter_map(|kind| kind.tag.map(|tag| tag.type_name))
+                .join(", "),
+            INSTRUCTION_ARGUMENT_TRAIT_NAME,
+            OPERAND_KIND_TYPE_NAME,
+            OPERAND_TYPE_NAME,
+            REGISTER_SET_TYPE_NAME
+        ))
+        .with_import(&format!(
+            "use {}::byte_sink::{{{}, {}, {}}}",
+            support_path, BYTE_SINK_TRAIT_NAME, BUFFER_TOO_SMALL_TYPE_NAME, ENCODE_ERROR_TYPE_NAME
+        ))
+        .with_import(&format!(
+            "use {}::instruction_info::{}",
+            support_path, INSTRUCTION_INFO_TYPE_NAME
+        ))
+        .with_import(&format!(
+            "use {}::opcode::{{self, {}}}",
+            support_path, UNKNOWN_OPCODE_TYPE_NAME
+        ))
+        .with_import(&format!(
+            "use {}::{}::{{{}}}",
+            support_path,
+            PRIVATE_MODULE_NAME,
+            BASE_IMPORTS.join(", ")
+        ));
+
+    if instruction_details.iter().any(|instruction| {
+        instruction
+            .indexed_fields()
+            .into_iter()
+            .any(|(kind, index)| instruction.compact_form(kind, index).is_some())
+    }) {
+        rust_file.push_import(&format!(
+            "use {}::encoding::{{self, {}}}",
+            support_path, COMPACT_FORM_TYPE_NAME
+        ));
+    }
+
+    if instruction_details
+        .iter()
+        .any(|instruction| instruction.extension.is_some())
+    {
+        rust_file.push_import(&format!(
+            "use {}::extension::{}",
+            support_path, EXTENSION_TYPE_NAME
+        ));
+    }
+
+    rust_file.push_component(create_opcode_enum(instruction_details).into());
+
+    push_opcode_components(&mut rust_file, instruction_details);
+
+    rust_file.push_component(generate_instructions_table(instruction_details).into());
+
+    rust_file.push_component(create_opcode_impl(instruction_details).into());
+
+    rust_file.push_component(generate_visitor_trait(instruction_details).into());
+
+    rust_file.push_component(generate_display_impl(instruction_details).into());
+
+    rust_file.push_component(generate_into_bytes_trait().into());
+
+    rust_file.push_component(generate_into_opcode_trait().into());
+
+    return rust_file.into_rust_code();
+}
+
+fn create_opcode_enum(instruction_details: &InstructionDetails) -> RustEnum {
+    let mut opcode_enum = RustEnum::new(OPCODE_ENUM_NAME)
+        .with_cfg(OPCODE_DERIVE_TRAITS)
+        .with_visibility(Visibility::Public);
+
+    for instruction in instruction_details {
+        let mut builder = EnumVariant::build(&instruction.name);
+
+        for field in &instruction.fields {
+            builder.push_value(field.type_name);
+        }
+
+        opcode_enum.push_variant(builder.build());
+    }
+
+    return opcode_enum;
+}
+
+fn create_opcode_impl(opcodes: &InstructionDetails) -> RustImplementation {
+    let mut opcode_impl = RustImplementation::new(OPCODE_ENUM_NAME);
+
+    opcode_impl.push_component(generate_new_opcode_method(opcodes).into());
+    opcode_impl.push_component(generate_from_bytes_method(opcodes).into());
+
+    opcode_impl.push_component(generate_opcode_method(opcodes).into());
+    opcode_impl.push_component(
+        RustText::new("/// Returns the description of this instruction's opcode.").into(),
+    );
+    opcode_impl.push_component(
+        RustMethod::new("info")
+            .with_visibility(Visibility::Public)
+            .with_argument("&self")
+            .with_return_type(&format!("&'static {}", INSTRUCTION_INFO_TYPE_NAME))
+            .with_body("return self.opcode().info();")
+            .into(),
+    );
+    opcode_impl.push_component(generate_into_assembly_method(opcodes).into());
+
+    opcode_impl.push_component(
+        RustText::new("/// The number of bytes the instruction takes up once encoded.").into(),
+    );
+    opcode_impl.push_component(
+        RustMethod::new("encoded_len")
+            .with_fn_type("const")
+            .with_visibility(Visibility::Public)
+            .with_argument("&self")
+            .with_return_type("usize")
+            .with_body("return self.opcode().encoded_len();")
+            .into(),
+    );
+    opcode_impl.push_component(
+        RustText::new(
+            "/// Encodes the instruction at the start of a buffer, returning the number of bytes written.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(generate_encode_into_method(opcodes).into());
+    opcode_impl.push_component(
+        RustText::new(
+            "/// Encodes the instruction into a sink without allocating, returning the number of bytes\n/// written.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(
+        RustMethod::new("encode_to")
+            .with_visibility(Visibility::Public)
+            .with_template(&format!("S: {}", BYTE_SINK_TRAIT_NAME))
+            .with_argument("&self")
+            .with_argument("sink: &mut S")
+            .with_return_type(&format!("Result<usize, {}<S::Error>>", ENCODE_ERROR_TYPE_NAME))
+            .with_body(&format!(
+                "let mut bytes = [0; {}::MAX_ENCODED_LEN];\nlet len = match self.encode_into(&mut bytes) {{\n\tOk(len) => len,\n\tErr({}::Sink(_)) => unreachable!(\"MAX_ENCODED_LEN holds every instruction\"),\n\tErr({}::DisplacementOutOfRange(displacement)) => return Err({}::DisplacementOutOfRange(displacement)),\n}};\n\nsink.write_bytes(&bytes[..len])?;\n\nreturn Ok(len);",
+                OPCODE_TYPE_NAME, ENCODE_ERROR_TYPE_NAME, ENCODE_ERROR_TYPE_NAME, ENCODE_ERROR_TYPE_NAME
+            ))
+            .into(),
+    );
+
+    opcode_impl.push_component(
+        RustText::new(
+            "/// Encodes the instruction in the compact encoding into a sink, returning the number of bytes\n/// written.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(generate_encode_compact_method(opcodes).into());
+    opcode_impl.push_component(
+        RustText::new(
+            "/// Decodes a single instruction in the compact encoding from the start of a byte slice,\n/// returning it along with the number of bytes it took up.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(generate_from_compact_bytes_method(opcodes).into());
+
+    opcode_impl.push_component(
+        RustText::new("/// The operands of the instruction, in the order they appear in assembly.")
+            .into(),
+    );
+    opcode_impl.push_component(generate_operands_method(opcodes).into());
+
+    for kind in OPERAND_KINDS.iter() {
+        opcode_impl.push_component(
+            RustText::new(&format!(
+                "/// Returns the instruction with every {} operand replaced by the result of `f`.",
+                kind.type_name
+            ))
+            .into(),
+        );
+        opcode_impl.push_component(generate_map_method(opcodes, kind).into());
+    }
+
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The registers the instruction reads, including those that are not operands.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(generate_register_use_method(opcodes, "reads", |i| &i.reads).into());
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The registers the instruction writes, including those that are not operands.",
+        )
+        .into(),
+    );
+    opcode_impl
+        .push_component(generate_register_use_method(opcodes, "writes", |i| &i.writes).into());
+    opcode_impl.push_component(
+        RustText::new("/// True if the instruction writes to the flags register.").into(),
+    );
+    opcode_impl.push_component(
+        generate_predicate_method(opcodes, "affects_flags", Instruction::affects_flags).into(),
+    );
+
+    for property in PROPERTIES.iter() {
+        opcode_impl.push_component(RustText::new(&format!("/// {}", property.doc)).into());
+        opcode_impl.push_component(
+            generate_predicate_method(opcodes, property.method, |instruction| {
+                instruction.properties.contains(property)
+            })
+            .into(),
+        );
+    }
+
+    return opcode_impl;
+}
+
+fn generate_into_assembly_method(instructions: &InstructionDetails) -> RustMethod {
+    let method = RustMethod::new("into_assembly")
+        .with_argument("self")
+        .with_visibility(Visibility::Public)
+        .with_return_type("String");
+
+    let mut body = String::from("return match self {\n");
+
+    for instruction in instructions {
+        if instruction.fields.is_empty() {
+            body.push_str(&format!(
+                "\tSelf::{} => \"{}\".to_string(),\n",
+                instruction.name, instruction.short_name
+            ));
+        } else {
+            let lhs = instruction
+                .indexed_fields()
+                .into_iter()
+                .map(|(kind, index)| kind.numbered_name(index))
+                .join(", ");
+
+            let rhs = instruction
+                .indexed_order()
+                .into_iter()
+                .map(|(kind, index)| kind.numbered_name(index))
+                .join(", ");
+
+            body.push_str(&format!(
+                "\tSelf::{}({}) => format!(\"{} {}\", {}),\n",
+                instruction.name,
+                lhs,
+                instruction.short_name,
+                instruction.argument_order.iter().map(|_| "{}").join(", "),
+                rhs
+            ));
+        }
+    }
+
+    return method.with_body(&format!("{}}};", body));
+}
+
+fn generate_operands_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut body = String::from("let operands = match self {\n");
+
+    for instruction in instructions.iter().filter(|i| !i.fields.is_empty()) {
+        body.push_str(&format!(
+            "\t{} => vec![{}],\n",
+            variant_pattern(instruction, |_, _| true),
+            instruction
+                .indexed_order()
+                .into_iter()
+                .map(|(kind, index)| format!(
+                    "{}::{}(*{})",
+                    OPERAND_TYPE_NAME,
+                    kind.type_name,
+                    kind.numbered_name(index)
+                ))
+                .join(", ")
+        ));
+    }
+
+    if instructions.iter().any(|i| i.fields.is_empty()) {
+        body.push_str("\t_ => Vec::new(),\n");
+    }
+
+    body.push_str("};\n\nreturn operands.into_iter();");
+
+    return RustMethod::new("operands")
+        .with_visibility(Visibility::Public)
+        .with_argument("&self")
+        .with_return_type(&format!("impl Iterator<Item = {}>", OPERAND_TYPE_NAME))
+        .with_body(&body);
+}
+
+/// Generates a method rebuilding the instruction with every operand of a kind passed through a
+/// closure, the remaining fields are left untouched.
+fn generate_map_method(instructions: &InstructionDetails, kind: &OperandKind) -> RustMethod {
+    let method = RustMethod::new(&format!("map_{}", kind.vector_name))
+        .with_visibility(Visibility::Public)
+        .with_argument("self")
+        .with_return_type("Self");
+    let closure = format!("impl FnMut({}) -> {}", kind.type_name, kind.type_name);
+
+    let mapped = instructions
+        .iter()
+        .filter(|instruction| instruction.count(kind) > 0)
+        .collect::<Vec<_>>();
+
+    if mapped.is_empty() {
+        return method
+            .with_argument(&format!("_f: {}", closure))
+            .with_body("return self;");
+    }
+
+    let mut body = String::from("return match self {\n");
+
+    for instruction in &mapped {
+        body.push_str(&format!(
+            "\t{} => Self::{}({}),\n",
+            variant_pattern(instruction, |_, _| true),
+            instruction.name,
+            instruction
+                .indexed_fields()
+                .into_iter()
+                .map(|(field_kind, index)| {
+                    if field_kind.symbol == kind.symbol {
+                        format!("f({})", field_kind.numbered_name(index))
+                    } else {
+                        field_kind.numbered_name(index)
+                    }
+                })
+                .join(", ")
+        ));
+    }
+
+    if mapped.len() < instructions.len() {
+        body.push_str("\t_ => self,\n");
+    }
+
+    body.push_str("};");
+
+    return method
+        .with_argument(&format!("mut f: {}", closure))
+        .with_body(&body);
+}
+
+/// Generates a method returning the registers an instruction reads or writes as a `RegisterSet`.
+fn generate_register_use_method(
+    instructions: &InstructionDetails,
+    name: &str,
+    uses: fn(&Instruction) -> &Vec<RegisterUse>,
+) -> RustMethod {
+    let mut body = String::from("return match self {\n");
+
+    for instruction in instructions.iter().filter(|i| !uses(i).is_empty()) {
+        let mut value = format!("{}::new()", REGISTER_SET_TYPE_NAME);
+
+        for register in uses(instruction) {
+            match register {
+                RegisterUse::Operand(index) => value.push_str(&format!(
+                    ".with(*{})",
+                    OperandKind::for_symbol(REGISTER_SYMBOL)
+                        .unwrap()
+                        .numbered_name(*index)
+                )),
+                RegisterUse::Implicit(variant) => {
+                    value.push_str(&format!(".with({}::{})", REGISTER_TYPE_NAME, variant))
+                }
+            }
+        }
+
+        body.push_str(&format!(
+            "\t{} => {},\n",
+            variant_pattern(instruction, |kind, index| {
+                kind.symbol == REGISTER_SYMBOL
+                    && uses(instruction).contains(&RegisterUse::Operand(index))
+            }),
+            value
+        ));
+    }
+
+    body.push_str(&format!("\t_ => {}::new(),\n}};", REGISTER_SET_TYPE_NAME));
+
+    return RustMethod::new(name)
+        .with_visibility(Visibility::Public)
+        .with_argument("&self")
+        .with_return_type(REGISTER_SET_TYPE_NAME)
+        .with_body(&body);
+}
+
+/// Generates a method returning true for the instructions matching a predicate.
+fn generate_predicate_method(
+    instructions: &InstructionDetails,
+    name: &str,
+    predicate: impl Fn(&Instruction) -> bool,
+) -> RustMethod {
+    let patterns = instructions
+        .iter()
+        .filter(|instruction| predicate(instruction))
+        .map(|instruction| variant_pattern(instruction, |_, _| false))
+        .join("\n\t\t| ");
+
+    let body = if patterns.is_empty() {
+        String::from("return false;")
+    } else {
+        format!("return matches!(\n\tself,\n\t{}\n);", patterns)
+    };
+
+    return RustMethod::new(name)
+        .with_fn_type("const")
+        .with_visibility(Visibility::Public)
+        .with_argument("&self")
+        .with_return_type("bool")
+        .with_body(&body);
+}
+
+/// A pattern matching a variant, binding the fields selected by `bind` to their numbered names and
+/// ignoring the rest.
+fn variant_pattern(
+    instruction: &Instruction,
+    bind: impl Fn(&OperandKind, usize) -> bool,
+) -> String {
+    if instruction.fields.is_empty() {
+        return format!("Self::{}", instruction.name);
+    }
+
+    let fields = instruction.indexed_fields();
+
+    if !fields.iter().any(|(kind, index)| bind(kind, *index)) {
+        return format!("Self::{}(..)", instruction.name);
+    }
+
+    return format!(
+        "Self::{}({})",
+        instruction.name,
+        fields
+            .into_iter()
+            .map(|(kind, index)| {
+                if bind(kind, index) {
+                    kind.numbered_name(index)
+                } else {
+                    String::from("_")
+                }
+            })
+            .join(", ")
+    );
+}
+
+fn generate_new_opcode_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut method = RustMethod::new("new").with_argument(&format!("opcode: {}", OPCODE_TYPE_NAME));
+
+    for kind in OPERAND_KINDS.iter() {
+        method.push_argument(&format!("{}: Vec<{}>", kind.vector_name, kind.type_name));
+    }
+
+    let mut body = format!(
+        "if {} {{\n\treturn None;\n}}\n\n",
+        OPERAND_KINDS
+            .iter()
+            .map(|kind| format!(
+                "{}.len() != opcode.{}()",
+                kind.vector_name, kind.count_method
+            ))
+            .join(" || ")
+    );
+
+    body.push_str(&format!(
+        "return Some(match opcode {{\n{}}});",
+        instructions
+            .iter()
+            .map(|instruction| {
+                if instruction.fields.is_empty() {
+                    format!(
+                        "\t{}::{} => Self::{},\n",
+                        OPCODE_TYPE_NAME, &instruction.name, &instruction.name
+                    )
+                } else {
+                    format!(
+                        "\t{}::{} => Self::{}({}),\n",
+                        OPCODE_TYPE_NAME,
+                        &instruction.name,
+                        &instruction.name,
+                        instruction
+                            .indexed_fields()
+                            .into_iter()
+                            .map(|(kind, index)| format!("{}[{}]", kind.vector_name, index))
+                            .join(", ")
+                    )
+                }
+            })
+            .collect::<String>()
+    ));
+
+    return method
+        .with_return_type("Option<Self>")
+        .with_visibility(Visibility::Public)
+        .with_body(&body);
+}
+
+/// Generates a method decoding a single instruction from the start of a byte slice. Operands are
+/// read in the order of `OPERAND_KINDS`, mirroring `generate_into_bytes_trait`.
+fn generate_from_bytes_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut body = format!(
+        "let (opcode, _) = {}::from_bytes(bytes)?;\nlet len = opcode.encoded_len();\nlet bytes = bytes.get(..len)?;\n\nlet instruction = match opcode {{\n",
+        OPCODE_TYPE_NAME
+    );
+
+    for instruction in instructions {
+        let fields = operand_layout(instruction)
+            .into_iter()
+            .map(|location| {
+                let (name, mut field) = match location {
+                    OperandLocation::Bytes {
+                        name, start, end, ..
+                    } => {
+                        let field = format!(
+                            "{}::from_bytes(&bytes[{}..{}])?",
+                            name.0.type_name, start, end
+                        );
+
+                        (name, field)
+                    }
+                    OperandLocation::HighNibble { name, byte } => {
+                        let field =
+                            format!("{}::from_bytes(&[bytes[{}] >> 4])?", name.0.type_name, byte);
+
+                        (name, field)
+                    }
+                    OperandLocation::LowNibble { name, byte } => {
+                        let field = format!(
+                            "{}::from_bytes(&[bytes[{}] & 0xf])?",
+                            name.0.type_name, byte
+                        );
+
+                        (name, field)
+                    }
+                };
+
+                // Tags are not encoded, they are restored from the table.
+                if let Some(tag) = name.0.tag {
+                    field.push_str(&format!(
+                        ".{}({}::{})",
+                        tag.method, tag.type_name, instruction.tags[&name.0.symbol][name.1]
+                    ));
+                }
+
+                field
+            })
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            body.push_str(&format!(
+                "\t{}::{} => Self::{},\n",
+                OPCODE_TYPE_NAME, instruction.name, instruction.name
+            ));
+        } else {
+            body.push_str(&format!(
+                "\t{}::{} => Self::{}(\n\t\t{},\n\t),\n",
+                OPCODE_TYPE_NAME,
+                instruction.name,
+                instruction.name,
+                fields.join(",\n\t\t")
+            ));
+        }
+    }
+
+    body.push_str("};\n\nreturn Some((instruction, len));");
+
+    return RustMethod::new("from_bytes")
+        .with_visibility(Visibility::Public)
+        .with_argument("bytes: &[u8]")
+        .with_return_type("Option<(Self, usize)>")
+        .with_body(&body);
+}
+
+fn generate_into_opcode_trait() -> RustImplementation {
+    let mut into_trait =
+        RustImplementation::new_for(&format!("Into<{}>", OPCODE_TYPE_NAME), OPCODE_ENUM_NAME);
+    let method = RustMethod::new("into")
+        .with_argument("self")
+        .with_return_type(OPCODE_TYPE_NAME)
+        .with_body("return self.opcode();");
+
+    into_trait.push_component(method.into());
+
+    return into_trait;
+}
+
+/// Generates a method returning the opcode of an instruction.
+fn generate_opcode_method(instructions: &InstructionDetails) -> RustMethod {
+    return RustMethod::new("opcode")
+        .with_fn_type("const")
+        .with_visibility(Visibility::Public)
+        .with_argument("&self")
+        .with_return_type(OPCODE_TYPE_NAME)
+        .with_body(&format!(
+            "return match self {{\n{}}};",
+            instructions
+                .iter()
+                .map(|instruction| format!(
+                    "\t{} => {}::{},\n",
+                    variant_pattern(instruction, |_, _| false),
+                    OPCODE_TYPE_NAME,
+                    instruction.name
+                ))
+                .collect::<String>()
+        ));
+}
+
+fn generate_encode_into_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut body = format!(
+        "let len = self.encoded_len();\n\nif buffer.len() < len {{\n\treturn Err({}::Sink({}::new(len, buffer.len())));\n}}\n\nmatch *self {{\n",
+        ENCODE_ERROR_TYPE_NAME, BUFFER_TOO_SMALL_TYPE_NAME
+    );
+
+    for instruction in instructions {
+        let layout = operand_layout(instruction);
+        let mut statements = width_checks(&layout);
+
+        statements.extend(
+            instruction
+                .opcode_bytes()
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| format!("buffer[{}] = {};", index, byte)),
+        );
+
+        for (position, location) in layout.iter().enumerate() {
+            match location {
+                OperandLocation::Bytes {
+                    name,
+                    start,
+                    end,
+                    width: None,
+                } => statements.push(format!(
+                    "buffer[{}..{}].copy_from_slice(&Into::<[u8; {}::BYTES]>::into({}));",
+                    start,
+                    end,
+                    name.0.type_name,
+                    name.0.numbered_name(name.1)
+                )),
+                OperandLocation::Bytes {
+                    name,
+                    start,
+                    end,
+                    width: Some(width),
+                } => statements.push(format!(
+                    "buffer[{}..{}].copy_from_slice(&Into::<[u8; {}::BYTES]>::into({})[..{}]);",
+                    start,
+                    end,
+                    name.0.type_name,
+                    name.0.numbered_name(name.1),
+                    width
+                )),
+                OperandLocation::HighNibble { name, byte } => {
+                    // The low nibble of a byte is always the operand following the high nibble.
+                    match layout.get(position + 1) {
+                        Some(OperandLocation::LowNibble { name: low, .. }) => {
+                            statements.push(format!(
+                                "buffer[{}] = ({} as u8) << 4 | ({} as u8);",
+                                byte,
+                                name.0.numbered_name(name.1),
+                                low.0.numbered_name(low.1)
+                            ))
+                        }
+                        _ => statements.push(format!(
+                            "buffer[{}] = ({} as u8) << 4;",
+                            byte,
+                            name.0.numbered_name(name.1)
+                        )),
+                    }
+                }
+                OperandLocation::LowNibble { .. } => (),
+            }
+        }
+
+        body.push_str(&format!(
+            "\t{} => {{\n",
+            variant_pattern(instruction, |_, _| true)
+        ));
+
+        for statement in statements {
+            body.push_str(&format!("\t\t{}\n", statement));
+        }
+
+        body.push_str("\t}\n");
+    }
+
+    body.push_str("}\n\nreturn Ok(len);");
+
+    return RustMethod::new("encode_into")
+        .with_visibility(Visibility::Public)
+        .with_argument("&self")
+        .with_argument("buffer: &mut [u8]")
+        .with_return_type(&format!(
+            "Result<usize, {}<{}>>",
+            ENCODE_ERROR_TYPE_NAME, BUFFER_TOO_SMALL_TYPE_NAME
+        ))
+        .with_body(&body);
+}
+
+/// Generates a method writing an instruction in the compact encoding. Operands are written in the
+/// same order as `encode_into`, only the values of kinds with a compact form change size.
+fn generate_encode_compact_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut body = format!(
+        "let mut buffer = [0; {}::MAX_COMPACT_LEN];\n\nlet len = match *self {{\n",
+        OPCODE_TYPE_NAME
+    );
+
+    for instruction in instructions {
+        let layout = operand_layout(instruction);
+        let opcode_bytes = instruction.opcode_bytes();
+        let mut statements = width_checks(&layout);
+
+        statements.extend(
+            opcode_bytes
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| format!("buffer[{}] = {};", index, byte)),
+        );
+
+        if !layout.is_empty() {
+            statements.push(format!("let mut len = {};", opcode_bytes.len()));
+        }
+
+        for (position, location) in layout.iter().enumerate() {
+            match location {
+                OperandLocation::Bytes { name, width, .. } => {
+                    let field = name.0.numbered_name(name.1);
+
+                    match instruction.compact_form(name.0, name.1) {
+                        Some(form) => statements.push(format!(
+                            "len += encoding::write_compact({}.as_u64(), {}::{}, &mut buffer[len..]);",
+                            field, COMPACT_FORM_TYPE_NAME, form
+                        )),
+                        None => {
+                            let width = match width {
+                                Some(width) => width.to_string(),
+                                None => format!("{}::BYTES", name.0.type_name),
+                            };
+
+                            statements.push(format!(
+                                "buffer[len..len + {}].copy_from_slice(&Into::<[u8; {}::BYTES]>::into({})[..{}]);",
+                                width, name.0.type_name, field, width
+                            ));
+                            statements.push(format!("len += {};", width));
+                        }
+                    }
+                }
+                OperandLocation::HighNibble { name, .. } => {
+                    match layout.get(position + 1) {
+                        Some(OperandLocation::LowNibble { name: low, .. }) => {
+                            statements.push(format!(
+                                "buffer[len] = ({} as u8) << 4 | ({} as u8);",
+                                name.0.numbered_name(name.1),
+                                low.0.numbered_name(low.1)
+                            ))
+                        }
+                        _ => statements.push(format!(
+                            "buffer[len] = ({} as u8) << 4;",
+                            name.0.numbered_name(name.1)
+                        )),
+                    }
+
+                    statements.push(String::from("len += 1;"));
+                }
+                OperandLocation::LowNibble { .. } => (),
+            }
+        }
+
+        if layout.is_empty() {
+            statements.push(opcode_bytes.len().to_string());
+        } else {
+            statements.push(String::from("len"));
+        }
+
+        body.push_str(&format!(
+            "\t{} => {{\n{}\t}}\n",
+            variant_pattern(instruction, |_, _| true),
+            statements
+                .iter()
+                .map(|statement| format!("\t\t{}\n", statement))
+                .collect::<String>()
+        ));
+    }
+
+    body.push_str("};\n\nsink.write_bytes(&buffer[..len])?;\n\nreturn Ok(len);");
+
+    return RustMethod::new("encode_compact_to")
+        .with_visibility(Visibility::Public)
+        .with_template(&format!("S: {}", BYTE_SINK_TRAIT_NAME))
+        .with_argument("&self")
+        .with_argument("sink: &mut S")
+        .with_return_type(&format!(
+            "Result<usize, {}<S::Error>>",
+            ENCODE_ERROR_TYPE_NAME
+        ))
+        .with_body(&body);
+}
+
+/// Generates a method decoding a single instruction in the compact encoding, mirroring
+/// `generate_encode_compact_method`.
+fn generate_from_compact_bytes_method(instructions: &InstructionDetails) -> RustMethod {
+    let mut body = format!(
+        "let (opcode, len) = {}::from_bytes(bytes)?;\n\nreturn Some(match opcode {{\n",
+        OPCODE_TYPE_NAME
+    );
+
+    for instruction in instructions {
+        let layout = operand_layout(instruction);
+
+        if layout.is_empty() {
+            body.push_str(&format!(
+                "\t{}::{} => (Self::{}, len),\n",
+                OPCODE_TYPE_NAME, instruction.name, instruction.name
+            ));
+
+            continue;
+        }
+
+        let mut statements = vec![String::from("let mut len = len;")];
+        let mut fields = Vec::new();
+
+        for location in &layout {
+            let (name, mut field) = match location {
+                OperandLocation::Bytes { name, width, .. } => {
+                    let value = name.0.numbered_name(name.1);
+
+                    match instruction.compact_form(name.0, name.1) {
+                        Some(form) => {
+                            statements.push(format!(
+                                "let ({}, read) = encoding::read_compact(bytes.get(len..)?, {}::{})?;",
+                                value, COMPACT_FORM_TYPE_NAME, form
+                            ));
+                            statements.push(String::from("len += read;"));
+
+                            (name, format!("{}::from({})", name.0.type_name, value))
+                        }
+                        None => {
+                            let width = match width {
+                                Some(width) => width.to_string(),
+                                None => format!("{}::BYTES", name.0.type_name),
+                            };
+
+                            statements.push(format!(
+                                "let {} = {}::from_bytes(bytes.get(len..len + {})?)?;",
+                                value, name.0.type_name, width
+                            ));
+                            statements.push(format!("len += {};", width));
+
+                            (name, value)
+                        }
+                    }
+                }
+                OperandLocation::HighNibble { name, .. } => {
+                    statements.push(format!("let byte{} = *bytes.get(len)?;", fields.len()));
+                    statements.push(String::from("len += 1;"));
+
+                    let field = format!(
+                        "{}::from_bytes(&[byte{} >> 4])?",
+                        name.0.type_name,
+                        fields.len()
+                    );
+
+                    (name, field)
+                }
+                OperandLocation::LowNibble { name, .. } => {
+                    let field = format!(
+                        "{}::from_bytes(&[byte{} & 0xf])?",
+                        name.0.type_name,
+                        fields.len() - 1
+                    );
+
+                    (name, field)
+                }
+            };
+
+            // Tags are not encoded, they are restored from the table.
+            if let Some(tag) = name.0.tag {
+                field.push_str(&format!(
+                    ".{}({}::{})",
+                    tag.method, tag.type_name, instruction.tags[&name.0.symbol][name.1]
+                ));
+            }
+
+            fields.push(field);
+        }
+
+        body.push_str(&format!(
+            "\t{}::{} => {{\n{}\n\t\t(Self::{}({}), len)\n\t}}\n",
+            OPCODE_TYPE_NAME,
+            instruction.name,
+            statements
+                .iter()
+                .map(|statement| format!("\t\t{}", statement))
+                .join("\n"),
+            instruction.name,
+            fields.join(", ")
+        ));
+    }
+
+    body.push_str("});");
+
+    return RustMethod::new("from_compact_bytes")
+        .with_visibility(Visibility::Public)
+        .with_argument("bytes: &[u8]")
+        .with_return_type("Option<(Self, usize)>")
+        .with_body(&body);
+}
+
+/// Where an operand is stored within an encoded instruction, along with its kind and its index
+/// amongst the operands of that kind. Operands with a declared width hold the number of bytes they
+/// are truncated to.
+enum OperandLocation {
+    Bytes {
+        name: (&'static OperandKind, usize),
+        start: Offset,
+        end: Offset,
+        width: Option<usize>,
+    },
+    HighNibble {
+        name: (&'static OperandKind, usize),
+        byte: Offset,
+    },
+    LowNibble {
+        name: (&'static OperandKind, usize),
+        byte: Offset,
+    },
+}
+
+/// Lays out the fields of an instruction in the order they are encoded, following the opcode.
+fn operand_layout(instruction: &Instruction) -> Vec<OperandLocation> {
+    let mut offset = Offset {
+        constant: instruction.opcode_bytes().len(),
+        sizes: Vec::new(),
+    };
+    let mut layout = Vec::new();
+    let mut high_nibble = true;
+
+    for (kind, index) in instruction.indexed_fields() {
+        let name = (kind, index);
+
+        match kind.encoding {
+            Encoding::Bytes => {
+                if !high_nibble {
+                    offset.constant += 1;
+                    high_nibble = true;
+                }
+
+                let start = offset.clone();
+                offset.sizes.push(kind.type_name);
+
+                layout.push(OperandLocation::Bytes {
+                    name,
+                    start,
+                    end: offset.clone(),
+                    width: None,
+                });
+            }
+            Encoding::Declared(_) => {
+                if !high_nibble {
+                    offset.constant += 1;
+                    high_nibble = true;
+                }
+
+                let start = offset.clone();
+                let width = instruction.width(kind, index);
+                offset.constant += width;
+
+                layout.push(OperandLocation::Bytes {
+                    name,
+                    start,
+                    end: offset.clone(),
+                    width: Some(width),
+                });
+            }
+            Encoding::Nibble if high_nibble => {
+                layout.push(OperandLocation::HighNibble {
+                    name,
+                    byte: offset.clone(),
+                });
+                high_nibble = false;
+            }
+            Encoding::Nibble => {
+                layout.push(OperandLocation::LowNibble {
+                    name,
+                    byte: offset.clone(),
+                });
+                offset.constant += 1;
+                high_nibble = true;
+            }
+        }
+    }
+
+    return layout;
+}
+
+/// Checks every operand with a declared width fits in it before anything is written, so a value
+/// too wide for its form fails to encode rather than encoding as a different value. Only
+/// displacements have declared widths.
+fn width_checks(layout: &[OperandLocation]) -> Vec<String> {
+    return layout
+        .iter()
+        .filter_map(|location| match location {
+            OperandLocation::Bytes {
+                name,
+                width: Some(width),
+                ..
+            } => Some(format!(
+                "if {}.min_bytes() > {} {{\n\t\t\treturn Err({}::DisplacementOutOfRange({}));\n\t\t}}",
+                name.0.numbered_name(name.1),
+                width,
+                ENCODE_ERROR_TYPE_NAME,
+                name.0.numbered_name(name.1)
+            )),
+            _ => None,
+        })
+        .collect();
+}
+
+/// The offset of a byte within an encoded instruction, kept symbolic so the generated code does not
+/// depend on the sizes of the operand types.
+#[derive(Default, Clone)]
+struct Offset {
+    constant: usize,
+    sizes: Vec<&'static str>,
+}
+
+impl std::fmt::Display for Offset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.constant)?;
+
+        for (count, size) in self.sizes.iter().dedup_with_count() {
+            if count == 1 {
+                write!(f, " + {}::BYTES", size)?;
+            } else {
+                write!(f, " + {} * {}::BYTES", count, size)?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn generate_into_bytes_trait() -> RustImplementation {
+    let method = RustMethod::new("into")
+        .with_argument("self")
+        .with_return_type("Vec<u8>")
+        .with_body(
+            "let mut bytes = vec![0; self.encoded_len()];\n\nif let Err(e) = self.encode_into(&mut bytes) {\n\tpanic!(\"{}\", e);\n}\n\nreturn bytes;",
+        );
+
+    return RustImplementation::new_for("Into<Vec<u8>>", OPCODE_ENUM_NAME)
+        .with_component(
+            RustText::new(
+                "/// Encodes the instruction in the fixed encoding. Panics if a displacement does not fit in\n/// the width its instruction declares, `encode_to` returns an error instead.",
+            )
+            .into(),
+        )
+        .with_component(method.into());
+}
+
+/// Generates the public table describing every instruction, indexed by opcode.
+fn generate_instructions_table(instructions: &InstructionDetails) -> RustText {
+    let entries = instructions
+        .iter()
+        .map(|instruction| {
+            format!(
+                "\t{}::new({:?}, {:?}, {}, {}::{}.encoded_len(), &[{}], &[{}], {}),\n",
+                INSTRUCTION_INFO_TYPE_NAME,
+                instruction.short_name,
+                instruction.description,
+                instruction.opcode_num,
+                OPCODE_TYPE_NAME,
+                instruction.name,
+                instruction
+                    .fields
+                    .iter()
+                    .map(|kind| format!("{}::{}", OPERAND_KIND_TYPE_NAME, kind.type_name))
+                    .join(", "),
+                instruction
+                    .argument_order
+                    .iter()
+                    .map(|kind| format!("{}::{}", OPERAND_KIND_TYPE_NAME, kind.type_name))
+                    .join(", "),
+                match &instruction.extension {
+                    Some(extension) => format!("Some({}::{})", EXTENSION_TYPE_NAME, extension),
+                    None => String::from("None"),
+                }
+            )
+        })
+        .collect::<String>();
+
+    return RustText::new(&format!(
+        "/// Describes every instruction in order of their opcodes.\npub const {}: [{}; {}] = [\n{}];\n",
+        INSTRUCTIONS_TABLE_NAME,
+        INSTRUCTION_INFO_TYPE_NAME,
+        instructions.len(),
+        entries
+    ));
+}
+
+fn generate_display_impl(instructions: &InstructionDetails) -> RustImplementation {
+    let mut display_impl = RustImplementation::new_for("core::fmt::Display", OPCODE_ENUM_NAME);
+
+    let method = RustMethod::new("fmt")
+        .with_argument("&self")
+        .with_argument("f: &mut core::fmt::Formatter<'_>")
+        .with_return_type("core::fmt::Result")
+        .with_body(&format!(
+            "return write!(f, \"{{}}\", match self {{\n{}}});",
+            instructions
+                .iter()
+                .map(|instruction| {
+                    let mut lhs = format!("\tSelf::{}", &instruction.name);
+
+                    if !instruction.fields.is_empty() {
+                        lhs.push('(');
+
+                        for _ in 0..instruction.fields.len() - 1 {
+                            lhs.push_str("_, ");
+                        }
+
+                        lhs.push_str("_)");
+                    }
+
+                    format!("{} => \"{}\",\n", lhs, &instruction.short_name)
+                })
+                .collect::<String>()
+        ));
+
+    display_impl.push_component(method.into());
+
+    return display_impl;
+}
@@ -0,0 +1,441 @@
+use crate::config::*;
+use crate::constants::*;
+use itertools::Itertools;
+use rmod_gen::rust_component::Visibility;
+use rmod_gen::{RustFile, RustImplementation, RustMethod, RustText};
+
+/// Adds the fieldless `Opcode` enum and its implementations to the instruction module.
+pub fn push_opcode_components(rust_file: &mut RustFile, instructions: &InstructionDetails) {
+    rust_file.push_component(create_opcode_type(instructions).into());
+    rust_file.push_component(create_opcode_type_impl(instructions).into());
+    rust_file.push_component(generate_from_str_impl(instructions).into());
+    rust_file.push_component(generate_try_from_impl("u16", "value").into());
+    rust_file.push_component(generate_try_from_impl("u8", "value as u16").into());
+    rust_file.push_component(generate_into_value_impl().into());
+    rust_file.push_component(generate_opcode_display_impl().into());
+}
+
+fn create_opcode_type(instructions: &InstructionDetails) -> RustText {
+    return RustText::new(&format!(
+        "/// The opcode of an instruction without its operands, the value of each variant is the opcode.\n{}\n#[repr(u16)]\npub enum {} {{\n{}}}",
+        OPCODE_TYPE_DERIVE_TRAITS,
+        OPCODE_TYPE_NAME,
+        instructions
+            .iter()
+            .map(|instruction| format!(
+                "    /// {}\n    {} = {},\n",
+                instruction.description, instruction.name, instruction.opcode_num
+            ))
+            .collect::<String>()
+    ));
+}
+
+fn create_opcode_type_impl(instructions: &InstructionDetails) -> RustImplementation {
+    let mut opcode_impl = RustImplementation::new(OPCODE_TYPE_NAME);
+
+    opcode_impl.push_component(
+        RustText::new(&format!(
+            "/// Every opcode in ascending order.\npub const ALL: [Self; {}] = [{}];\n",
+            instructions.len(),
+            instructions
+                .iter()
+                .map(|instruction| format!("Self::{}", instruction.name))
+                .join(", ")
+        ))
+        .into(),
+    );
+
+    opcode_impl
+        .push_component(RustText::new("/// Iterates over every opcode in ascending order.").into());
+    opcode_impl.push_component(
+        RustMethod::new("iter")
+            .with_visibility(Visibility::Public)
+            .with_return_type(&format!("impl Iterator<Item = {}>", OPCODE_TYPE_NAME))
+            .with_body("return Self::ALL.into_iter();")
+            .into(),
+    );
+
+    opcode_impl.push_component(
+        RustMethod::new("value")
+            .with_fn_type("const")
+            .with_visibility(Visibility::Public)
+            .with_argument("self")
+            .with_return_type("u16")
+            .with_body("return self as u16;")
+            .into(),
+    );
+
+    opcode_impl.push_component(
+        RustText::new("/// Returns the opcode with a value, or None if no instruction uses it.")
+            .into(),
+    );
+    opcode_impl.push_component(
+        RustMethod::new("from_value")
+            .with_fn_type("const")
+            .with_visibility(Visibility::Public)
+            .with_argument("value: u16")
+            .with_return_type("Option<Self>")
+            .with_body(&format!(
+                "return Some(match value {{\n{}\t_ => return None,\n}});",
+                instructions
+                    .iter()
+                    .map(|instruction| format!(
+                        "\t{} => Self::{},\n",
+                        instruction.opcode_num, instruction.name
+                    ))
+                    .collect::<String>()
+            ))
+            .into(),
+    );
+
+    opcode_impl
+        .push_component(RustText::new("/// The name of the instruction used in assembly.").into());
+    opcode_impl.push_component(
+        generate_match_method("mnemonic", "&'static str", instructions, |instruction| {
+            format!("\"{}\"", instruction.short_name)
+        })
+        .into(),
+    );
+
+    opcode_impl.push_component(
+        RustText::new("/// The position of the opcode in `ALL` and `INSTRUCTIONS`.").into(),
+    );
+    opcode_impl.push_component(
+        generate_match_method("index", "usize", instructions, |instruction| {
+            instructions
+                .iter()
+                .position(|other| other.opcode_num == instruction.opcode_num)
+                .unwrap()
+                .to_string()
+        })
+        .into(),
+    );
+
+    opcode_impl.push_component(
+        RustMethod::new("info")
+            .with_visibility(Visibility::Public)
+            .with_argument("self")
+            .with_return_type(&format!("&'static {}", INSTRUCTION_INFO_TYPE_NAME))
+            .with_body(&format!(
+                "return &{}[self.index()];",
+                INSTRUCTIONS_TABLE_NAME
+            ))
+            .into(),
+    );
+
+    for kind in OPERAND_KINDS.iter() {
+        opcode_impl.push_component(
+            generate_match_method(kind.count_method, "usize", instructions, |instruction| {
+                instruction.count(kind).to_string()
+            })
+            .into(),
+        );
+    }
+
+    opcode_impl.push_component(generate_type_ordering_method(instructions).into());
+
+    for kind in OPERAND_KINDS.iter() {
+        if let Some(tag) = kind.tag {
+            opcode_impl.push_component(
+                RustText::new(&format!(
+                    "/// The {} declared for the {} operand at an index, None if there is no such operand.",
+                    tag.column.to_lowercase().trim_end_matches('s'),
+                    kind.type_name.to_lowercase()
+                ))
+                .into(),
+            );
+            opcode_impl.push_component(generate_tag_method(kind, tag, instructions).into());
+        }
+    }
+
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The number of bytes an instruction with this opcode takes up once encoded, including the\n/// opcode.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(
+        generate_match_method("encoded_len", "usize", instructions, encoded_len_expression).into(),
+    );
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The largest number of bytes any instruction takes up once encoded.\npub const MAX_ENCODED_LEN: usize = {\n    let mut max = 0;\n    let mut index = 0;\n\n    while index < Self::ALL.len() {\n        if Self::ALL[index].encoded_len() > max {\n            max = Self::ALL[index].encoded_len();\n        }\n\n        index += 1;\n    }\n\n    max\n};\n",
+        )
+        .into(),
+    );
+
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The largest number of bytes an instruction with this opcode takes up in the compact\n/// encoding, including the opcode.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(
+        generate_match_method(
+            "max_compact_len",
+            "usize",
+            instructions,
+            max_compact_len_expression,
+        )
+        .into(),
+    );
+    opcode_impl.push_component(
+        RustText::new(
+            "/// The largest number of bytes any instruction takes up in the compact encoding.\npub const MAX_COMPACT_LEN: usize = {\n    let mut max = 0;\n    let mut index = 0;\n\n    while index < Self::ALL.len() {\n        if Self::ALL[index].max_compact_len() > max {\n            max = Self::ALL[index].max_compact_len();\n        }\n\n        index += 1;\n    }\n\n    max\n};\n",
+        )
+        .into(),
+    );
+
+    opcode_impl.push_component(
+        RustText::new(
+            "/// Decodes the opcode at the start of a byte slice, returning it along with the number of bytes\n/// it took up.",
+        )
+        .into(),
+    );
+    opcode_impl.push_component(
+        RustMethod::new("from_bytes")
+            .with_visibility(Visibility::Public)
+            .with_argument("bytes: &[u8]")
+            .with_return_type("Option<(Self, usize)>")
+            .with_body("let (value, length) = opcode::decode(bytes)?;\n\nreturn Some((Self::from_value(value)?, length));")
+            .into(),
+    );
+
+    opcode_impl.push_component(
+        RustMethod::new("into_bytes")
+            .with_visibility(Visibility::Public)
+            .with_argument("self")
+            .with_return_type("Vec<u8>")
+            .with_body("return opcode::encode(self as u16);")
+            .into(),
+    );
+
+    return opcode_impl;
+}
+
+/// Generates a const method matching on the opcode with an arm for each instruction.
+fn generate_match_method(
+    name: &str,
+    return_type: &str,
+    instructions: &InstructionDetails,
+    value: impl Fn(&Instruction) -> String,
+) -> RustMethod {
+    return RustMethod::new(name)
+        .with_fn_type("const")
+        .with_visibility(Visibility::Public)
+        .with_argument("self")
+        .with_return_type(return_type)
+        .with_body(&format!(
+            "return match self {{\n{}}};",
+            instructions
+                .iter()
+                .map(|instruction| format!(
+                    "\tSelf::{} => {},\n",
+                    instruction.name,
+                    value(instruction)
+                ))
+                .collect::<String>()
+        ));
+}
+
+fn generate_type_ordering_method(instructions: &InstructionDetails) -> RustMethod {
+    let body = format!(
+        "let ordering: &[u8] = match self {{\n{}}};\n\nif index >= ordering.len() {{\n\treturn None;\n}}\n\nreturn Some(ordering[index]);\n",
+        instructions
+            .iter()
+            .map(|instruction| {
+                format!(
+                    "\tSelf::{} => &[{}],\n",
+                    &instruction.name,
+                    instruction
+                        .argument_order
+                        .iter()
+                        .map(|kind| kind.type_code.to_string())
+                        .join(", ")
+                )
+            })
+            .collect::<String>()
+    );
+
+    return RustMethod::new("get_type_for_index")
+        .with_fn_type("const")
+        .with_visibility(Visibility::Public)
+        .with_argument("self")
+        .with_argument("index: usize")
+        .with_return_type("Option<u8>")
+        .with_body(&body);
+}
+
+/// Generates a method returning the tag declared for the operand at an index amongst the operands of
+/// a kind, e.g. `address_kind`.
+fn generate_tag_method(
+    kind: &OperandKind,
+    tag: &OperandTag,
+    instructions: &InstructionDetails,
+) -> RustMethod {
+    let name = format!(
+        "{}_{}",
+        kind.type_name.to_lowercase(),
+        tag.method.trim_start_matches("with_")
+    );
+    let body = format!(
+        "let tags: &[{}] = match self {{\n{}}};\n\nif index >= tags.len() {{\n\treturn None;\n}}\n\nreturn Some(tags[index]);\n",
+        tag.type_name,
+        instructions
+            .iter()
+            .map(|instruction| {
+                format!(
+                    "\tSelf::{} => &[{}],\n",
+                    instruction.name,
+                    instruction
+                        .tags
+                        .get(&kind.symbol)
+                        .into_iter()
+                        .flatten()
+                        .map(|variant| format!("{}::{}", tag.type_name, variant))
+                        .join(", ")
+                )
+            })
+            .collect::<String>()
+    );
+    return RustMethod::new(&name)
+        .with_fn_type("const")
+        .with_visibility(Visibility::Public)
+        .with_argument("self")
+        .with_argument("index: usize")
+        .with_return_type(&format!("Option<{}>", tag.type_name))
+        .with_body(&body);
+}
+
+fn generate_from_str_impl(instructions: &InstructionDetails) -> RustImplementation {
+    let mut from_str = RustImplementation::new_for("core::str::FromStr", OPCODE_TYPE_NAME);
+
+    from_str.push_component(
+        RustText::new(&format!("type Err = {};\n", UNKNOWN_OPCODE_TYPE_NAME)).into(),
+    );
+    from_str.push_component(
+        RustMethod::new("from_str")
+            .with_argument("s: &str")
+            .with_return_type("Result<Self, Self::Err>")
+            .with_body(&format!(
+                "return match s {{\n{}\t_ => Err({}),\n}};",
+                instructions
+                    .iter()
+                    .map(|instruction| format!(
+                        "\t\"{}\" => Ok(Self::{}),\n",
+                        instruction.short_name, instruction.name
+                    ))
+                    .collect::<String>(),
+                UNKNOWN_OPCODE_TYPE_NAME
+            ))
+            .into(),
+    );
+
+    return from_str;
+}
+
+fn generate_try_from_impl(source: &str, conversion: &str) -> RustImplementation {
+    let mut try_from =
+        RustImplementation::new_for(&format!("TryFrom<{}>", source), OPCODE_TYPE_NAME);
+
+    try_from.push_component(
+        RustText::new(&format!("type Error = {};\n", UNKNOWN_OPCODE_TYPE_NAME)).into(),
+    );
+    try_from.push_component(
+        RustMethod::new("try_from")
+            .with_argument(&format!("value: {}", source))
+            .with_return_type("Result<Self, Self::Error>")
+            .with_body(&format!(
+                "return Self::from_value({}).ok_or({});",
+                conversion, UNKNOWN_OPCODE_TYPE_NAME
+            ))
+            .into(),
+    );
+
+    return try_from;
+}
+
+fn generate_into_value_impl() -> RustImplementation {
+    let mut into_value = RustImplementation::new_for("Into<u16>", OPCODE_TYPE_NAME);
+
+    into_value.push_component(
+        RustMethod::new("into")
+            .with_argument("self")
+            .with_return_type("u16")
+            .with_body("return self as u16;")
+            .into(),
+    );
+
+    return into_value;
+}
+
+fn generate_opcode_display_impl() -> RustImplementation {
+    let mut display_impl = RustImplementation::new_for("core::fmt::Display", OPCODE_TYPE_NAME);
+
+    display_impl.push_component(
+        RustMethod::new("fmt")
+            .with_argument("&self")
+            .with_argument("f: &mut core::fmt::Formatter<'_>")
+            .with_return_type("core::fmt::Result")
+            .with_body("return write!(f, \"{}\", self.mnemonic());")
+            .into(),
+    );
+
+    return display_impl;
+}
+
+/// An expression for the number of bytes an instruction takes up once encoded, including the opcode.
+fn encoded_len_expression(instruction: &Instruction) -> String {
+    let mut terms = vec![instruction.opcode_bytes().len().to_string()];
+    let mut nibbles = 0;
+
+    for kind in OPERAND_KINDS.iter() {
+        let count = instruction.count(kind);
+
+        match kind.encoding {
+            Encoding::Bytes if count == 1 => terms.push(format!("{}::BYTES", kind.type_name)),
+            Encoding::Bytes if count > 1 => {
+                terms.push(format!("{} * {}::BYTES", count, kind.type_name))
+            }
+            Encoding::Bytes => (),
+            Encoding::Nibble => nibbles += count,
+            Encoding::Declared(_) if count > 0 => terms.push(
+                (0..count)
+                    .map(|index| instruction.width(kind, index))
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            Encoding::Declared(_) => (),
+        }
+    }
+
+    if nibbles > 0 {
+        terms.push(nibbles.div_ceil(2).to_string());
+    }
+
+    return terms.join(" + ");
+}
+
+/// An expression for the largest number of bytes an instruction takes up in the compact encoding,
+/// including the opcode.
+fn max_compact_len_expression(instruction: &Instruction) -> String {
+    let mut terms = vec![instruction.opcode_bytes().len().to_string()];
+    let mut nibbles: usize = 0;
+
+    for (kind, index) in instruction.indexed_fields() {
+        match (&kind.encoding, instruction.compact_form(kind, index)) {
+            (_, Some(form)) if form != "Fixed" => {
+                terms.push(String::from("encoding::MAX_COMPACT_VALUE_LEN"))
+            }
+            (Encoding::Nibble, _) => nibbles += 1,
+            (Encoding::Declared(_), _) => terms.push(instruction.width(kind, index).to_string()),
+            (Encoding::Bytes, _) => terms.push(format!("{}::BYTES", kind.type_name)),
+        }
+    }
+
+    if nibbles > 0 {
+        terms.push(nibbles.div_ceil(2).to_string());
+    }
+
+    return terms.join(" + ");
+}
@@ -0,0 +1,93 @@
+use crate::config::*;
+use crate::constants::*;
+use itertools::Itertools;
+use rmod_gen::rust_component::Visibility;
+use rmod_gen::{RustMethod, RustText, RustTrait};
+
+/// Generates the visitor trait, with a method per instruction that falls back to `visit_default`.
+pub fn generate_visitor_trait(instructions: &InstructionDetails) -> RustTrait {
+    let mut visitor_trait = RustTrait::new(VISITOR_TRAIT_NAME)
+        .with_visibility(Visibility::Public)
+        .with_component(RustText::new("type Output;\n").into())
+        .with_component(
+            RustText::new("/// Called for every instruction whose method is not overridden.")
+                .into(),
+        )
+        .with_component(
+            RustMethod::new("visit_default")
+                .with_argument("&mut self")
+                .with_argument(&format!("instruction: {}", OPCODE_ENUM_NAME))
+                .with_return_type("Self::Output")
+                .without_body()
+                .into(),
+        );
+
+    let dispatch = instructions
+        .iter()
+        .map(|instruction| {
+            let fields = field_names(instruction);
+
+            if fields.is_empty() {
+                format!(
+                    "\t{}::{} => self.visit_{}(),\n",
+                    OPCODE_ENUM_NAME, instruction.name, instruction.short_name
+                )
+            } else {
+                format!(
+                    "\t{}::{}({}) => self.visit_{}({}),\n",
+                    OPCODE_ENUM_NAME, instruction.name, fields, instruction.short_name, fields
+                )
+            }
+        })
+        .collect::<String>();
+
+    visitor_trait.push_component(
+        RustText::new("/// Calls the method for the instruction with its operands.").into(),
+    );
+    visitor_trait.push_component(
+        RustMethod::new("visit")
+            .with_argument("&mut self")
+            .with_argument(&format!("instruction: {}", OPCODE_ENUM_NAME))
+            .with_return_type("Self::Output")
+            .with_body(&format!("return match instruction {{\n{}}};", dispatch))
+            .into(),
+    );
+
+    for instruction in instructions {
+        let mut method = RustMethod::new(&format!("visit_{}", instruction.short_name))
+            .with_argument("&mut self")
+            .with_return_type("Self::Output");
+
+        for (kind, index) in instruction.indexed_fields() {
+            method.push_argument(&format!("{}: {}", kind.field_name(index), kind.type_name));
+        }
+
+        let fields = field_names(instruction);
+
+        if fields.is_empty() {
+            method.set_body(&format!(
+                "return self.visit_default({}::{});",
+                OPCODE_ENUM_NAME, instruction.name
+            ));
+        } else {
+            method.set_body(&format!(
+                "return self.visit_default({}::{}({}));",
+                OPCODE_ENUM_NAME, instruction.name, fields
+            ));
+        }
+
+        visitor_trait
+            .push_component(RustText::new(&format!("/// {}", instruction.description)).into());
+        visitor_trait.push_component(method.into());
+    }
+
+    return visitor_trait;
+}
+
+fn field_names(instruction: &Instruction) -> String {
+    return instruction
+        .indexed_fields()
+        .into_iter()
+        .map(|(kind, index)| kind.field_name(index))
+        .join(", ");
+}
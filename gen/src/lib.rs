@@ -0,0 +1,211 @@
+/*
+This generator is very messy and it will be hard to expand in the future but for the moment it works and will do.
+*/
+#![allow(clippy::needless_return, clippy::redundant_static_lifetimes)]
+//! Generates the `Instruction` enum, its encoder, decoder and assembly printer and the
+//! `ExecuteInstruction` trait from an instruction set table. This is used by the build script of
+//! `vxl-iset` for the base instruction set and can be used by other crates, either from a build
+//! script or through the `vxl_isa!` macro, to generate the same code for their own tables. The
+//! generated code reuses the operand types of `vxl-iset`.
+//!
+//! ```ignore
+//! // build.rs
+//! let generator = vxl_iset_gen::Generator::from_path("my_set.csv").unwrap();
+//! generator.write_to(std::env::var("OUT_DIR").unwrap()).unwrap();
+//! ```
+//!
+//! The files written are `instruction.rs` and `execute_instruction.rs`, they should be included
+//! into sibling modules named `instruction` and `execute_instruction`.
+
+mod config;
+mod constants;
+mod gen_execute_instruction;
+mod gen_instruction;
+mod gen_opcode;
+mod gen_visitor;
+mod validate;
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Instruction, InstructionDetails, Row};
+use constants::*;
+use gen_execute_instruction::generate_execute_trait;
+use gen_instruction::generate_instruction_file;
+use validate::validate_rows;
+pub use validate::ValidationError;
+
+/// The path of the `vxl-iset` crate used by code generated for other crates.
+pub const DEFAULT_SUPPORT_PATH: &'static str = "::vxl_iset";
+
+/// An error loading an instruction set table.
+#[derive(Debug)]
+pub enum Error {
+    /// The file could not be read or written.
+    Io(PathBuf, io::Error),
+    /// A row could not be read, holding its line and the reason.
+    Csv(u64, String),
+    /// The table was read but is not a valid instruction set.
+    Invalid(PathBuf, Vec<ValidationError>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::Io(path, e) => {
+                write!(
+                    f,
+                    "Failed to access the file {}. Error: {}",
+                    path.display(),
+                    e
+                )
+            }
+            Error::Csv(line, e) => write!(f, "Could not deserialize row {}. Error: {}", line, e),
+            Error::Invalid(path, errors) => {
+                write!(f, "Invalid instruction set {}:", path.display())?;
+
+                for error in errors {
+                    write!(f, "\n{}", error)?;
+                }
+
+                Ok(())
+            }
+        };
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generates code for an instruction set table and any extension tables.
+pub struct Generator {
+    instructions: InstructionDetails,
+    support_path: String,
+}
+
+impl Generator {
+    /// Loads and validates the instruction set table at a path. The table can use every opcode
+    /// that is not used by an extension, opcodes from `0xff00` are encoded with an escape prefix.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        return Ok(Self {
+            instructions: load_table(path.as_ref(), 0..=u16::MAX, None)?,
+            support_path: DEFAULT_SUPPORT_PATH.to_string(),
+        });
+    }
+
+    /// Adds the instructions of an extension table, the opcodes of the table must be numbered from
+    /// the start of its range and the instructions are generated as part of `Extension::{variant}`.
+    pub fn with_extension<P: AsRef<Path>>(
+        mut self,
+        path: P,
+        variant: &str,
+        opcode_range: RangeInclusive<u16>,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let instructions = load_table(path, opcode_range, Some(variant))?;
+        let mut errors = Vec::new();
+
+        for instruction in &instructions {
+            if let Some(existing) = self.instructions.iter().find(|existing| {
+                existing.opcode_num == instruction.opcode_num
+                    || existing.short_name == instruction.short_name
+            }) {
+                errors.push(ValidationError::new(
+                    instruction.line,
+                    format!(
+                        "{} ({}) conflicts with {} ({}) of another table",
+                        instruction.short_name,
+                        instruction.opcode_num,
+                        existing.short_name,
+                        existing.opcode_num
+                    ),
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::Invalid(path.to_path_buf(), errors));
+        }
+
+        self.instructions.extend(instructions);
+        self.instructions
+            .sort_by_key(|instruction| instruction.opcode_num);
+
+        return Ok(self);
+    }
+
+    /// Sets the path the generated code imports the operand types and support traits from,
+    /// `DEFAULT_SUPPORT_PATH` by default.
+    pub fn with_support_path(mut self, support_path: &str) -> Self {
+        self.support_path = support_path.to_string();
+
+        return self;
+    }
+
+    /// The code of the instruction module.
+    pub fn instruction_code(&self) -> String {
+        return generate_instruction_file(&self.instructions, &self.support_path);
+    }
+
+    /// The code of the execute instruction module.
+    pub fn execute_code(&self) -> String {
+        return generate_execute_trait(&self.instructions, &self.support_path);
+    }
+
+    /// Writes both modules into a directory.
+    pub fn write_to<P: AsRef<Path>>(&self, directory: P) -> Result<(), Error> {
+        for (file_name, code) in [
+            (OPCODE_OUTPUT_FILE, self.instruction_code()),
+            (EXECUTE_OUTPUT_FILE, self.execute_code()),
+        ] {
+            let path = directory.as_ref().join(file_name);
+
+            fs::write(&path, code).map_err(|e| Error::Io(path, e))?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// Loads and validates a single instruction set table.
+fn load_table(
+    path: &Path,
+    opcode_range: RangeInclusive<u16>,
+    extension: Option<&str>,
+) -> Result<InstructionDetails, Error> {
+    let file = File::open(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+    let mut csv_reader = csv::Reader::from_reader(file);
+    let mut rows = Vec::new();
+
+    for record in csv_reader.records() {
+        let record = record.map_err(|e| {
+            Error::Csv(
+                e.position().map_or(0, |position| position.line()),
+                e.to_string(),
+            )
+        })?;
+        let line = record.position().map_or(0, |position| position.line());
+
+        match record.deserialize::<Row>(None) {
+            Ok(r) => rows.push((line, r)),
+            Err(e) => return Err(Error::Csv(line, e.to_string())),
+        }
+    }
+
+    validate_rows(&rows, opcode_range)
+        .map_err(|errors| Error::Invalid(path.to_path_buf(), errors))?;
+
+    return Ok(rows
+        .into_iter()
+        .map(|(line, row)| {
+            let mut instruction = Instruction::from(row);
+
+            instruction.line = line;
+            instruction.extension = extension.map(str::to_string);
+
+            instruction
+        })
+        .collect());
+}
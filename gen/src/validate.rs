@@ -0,0 +1,356 @@
+use crate::config::{
+    Encoding, OperandKind, Property, RegisterUse, Row, ESCAPE_BYTE, FIRST_ESCAPED_OPCODE,
+    OPERAND_KINDS,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A problem with a row of the instruction set, identified by its line in the csv file.
+#[derive(Debug)]
+pub struct ValidationError {
+    line: u64,
+    message: String,
+}
+
+impl ValidationError {
+    pub(crate) fn new(line: u64, message: String) -> Self {
+        return Self { line, message };
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "Row {}: {}", self.line, self.message);
+    }
+}
+
+/// Checks the rows of an instruction set table, each paired with its line in the csv file. The
+/// opcodes of the table must be numbered from the start of the range without leaving it, the two
+/// byte opcodes continue from the start of the escaped page. Every problem found is returned rather
+/// than just the first.
+pub fn validate_rows(
+    rows: &[(u64, Row)],
+    opcode_range: RangeInclusive<u16>,
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    let mut opcodes: HashMap<u16, u64> = HashMap::new();
+    let mut mnemonics: HashMap<&str, u64> = HashMap::new();
+    let mut expected_opcode: u32 = *opcode_range.start() as u32;
+
+    for (line, row) in rows {
+        let line = *line;
+
+        if row.opcode.is_empty() {
+            errors.push(ValidationError::new(
+                line,
+                "The opcode name is empty".to_string(),
+            ));
+        }
+
+        let duplicate = match opcodes.get(&row.decimal) {
+            Some(first) => {
+                errors.push(ValidationError::new(
+                    line,
+                    format!(
+                        "Duplicate opcode {}, first used on row {}",
+                        row.decimal, first
+                    ),
+                ));
+
+                true
+            }
+            None => {
+                opcodes.insert(row.decimal, line);
+
+                false
+            }
+        };
+
+        match mnemonics.get(row.opcode.as_str()) {
+            Some(first) => errors.push(ValidationError::new(
+                line,
+                format!(
+                    "Duplicate opcode name {}, first used on row {}",
+                    row.opcode, first
+                ),
+            )),
+            None => {
+                mnemonics.insert(&row.opcode, line);
+            }
+        }
+
+        let escaped = row.decimal >= FIRST_ESCAPED_OPCODE;
+        let digits = if escaped { 2 } else { 1 };
+
+        check_number_column(
+            &mut errors,
+            line,
+            "Binary",
+            &row.binary,
+            2,
+            8 * digits,
+            row.decimal,
+        );
+        check_number_column(
+            &mut errors,
+            line,
+            "Hex",
+            &row.hex,
+            16,
+            2 * digits,
+            row.decimal,
+        );
+        check_ordering(&mut errors, line, row);
+        check_semantics(&mut errors, line, row);
+
+        let opcode = row.decimal as u32;
+
+        if escaped && expected_opcode < FIRST_ESCAPED_OPCODE as u32 {
+            expected_opcode = FIRST_ESCAPED_OPCODE as u32;
+        }
+
+        if !escaped && row.decimal >= ESCAPE_BYTE as u16 {
+            errors.push(ValidationError::new(
+                line,
+                format!(
+                    "Opcode {} cannot be encoded, single byte opcodes must be below {} and two byte opcodes from {}",
+                    opcode, ESCAPE_BYTE, FIRST_ESCAPED_OPCODE
+                ),
+            ));
+        } else if !opcode_range.contains(&row.decimal) {
+            errors.push(ValidationError::new(
+                line,
+                format!(
+                    "Opcode {} is outside of the range {} to {} of the table",
+                    opcode,
+                    opcode_range.start(),
+                    opcode_range.end()
+                ),
+            ));
+        } else if opcode > expected_opcode {
+            errors.push(ValidationError::new(
+                line,
+                format!(
+                    "Gap in numbering, opcodes {} to {} are missing",
+                    expected_opcode,
+                    opcode - 1
+                ),
+            ));
+        } else if opcode < expected_opcode && !duplicate {
+            errors.push(ValidationError::new(
+                line,
+                format!(
+                    "Opcode {} is out of order, expected {}",
+                    opcode, expected_opcode
+                ),
+            ));
+        }
+
+        expected_opcode = expected_opcode.max(opcode + 1);
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    } else {
+        return Err(errors);
+    }
+}
+
+fn check_number_column(
+    errors: &mut Vec<ValidationError>,
+    line: u64,
+    column: &str,
+    value: &str,
+    radix: u32,
+    width: usize,
+    decimal: u16,
+) {
+    match u16::from_str_radix(value, radix) {
+        Ok(v) if v == decimal && value.len() == width => (),
+        Ok(v) if v == decimal => errors.push(ValidationError::new(
+            line,
+            format!(
+                "{} column {} should be {} digits long",
+                column, value, width
+            ),
+        )),
+        Ok(v) => errors.push(ValidationError::new(
+            line,
+            format!(
+                "{} column {} is {} but the decimal column is {}",
+                column, value, v, decimal
+            ),
+        )),
+        Err(_) => errors.push(ValidationError::new(
+            line,
+            format!("{} column {} is not a valid number", column, value),
+        )),
+    }
+}
+
+fn check_ordering(errors: &mut Vec<ValidationError>, line: u64, row: &Row) {
+    for symbol in row.order.chars() {
+        if OperandKind::for_symbol(symbol).is_none() {
+            errors.push(ValidationError::new(
+                line,
+                format!("Unknown operand {} in the friendly ordering", symbol),
+            ));
+        }
+    }
+
+    for kind in OPERAND_KINDS.iter() {
+        let ordered = row.order.chars().filter(|c| *c == kind.symbol).count();
+        let declared = (kind.row_count)(row) as usize;
+
+        if ordered != declared {
+            errors.push(ValidationError::new(
+                line,
+                format!(
+                    "{} {} operands declared but the friendly ordering {:?} has {}",
+                    declared, kind.type_name, row.order, ordered
+                ),
+            ));
+        }
+    }
+}
+
+fn check_semantics(errors: &mut Vec<ValidationError>, line: u64, row: &Row) {
+    for (column, list) in [("Reads", &row.reads), ("Writes", &row.writes)] {
+        if let Err(message) = RegisterUse::parse_list(list, row.registers) {
+            errors.push(ValidationError::new(
+                line,
+                format!("{} in the {} column", message, column),
+            ));
+        }
+    }
+
+    if let Err(message) = Property::parse_list(&row.properties) {
+        errors.push(ValidationError::new(line, message));
+    }
+
+    for kind in OPERAND_KINDS.iter() {
+        if let Some(Err(message)) = kind
+            .tag
+            .map(|tag| tag.parse_list(row, (kind.row_count)(row)))
+        {
+            errors.push(ValidationError::new(line, message));
+        }
+
+        if let Encoding::Declared(widths) = kind.encoding {
+            if let Err(message) = widths.parse_list(row, (kind.row_count)(row)) {
+                errors.push(ValidationError::new(line, message));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(decimal: u16, opcode: &str, registers: u8, order: &str) -> Row {
+        return Row {
+            binary: format!("{:08b}", decimal),
+            decimal,
+            hex: format!("{:02X}", decimal),
+            opcode: opcode.to_string(),
+            description: String::new(),
+            immediates: 0,
+            registers,
+            addresses: 0,
+            conditions: 0,
+            order: order.to_string(),
+            reads: String::new(),
+            writes: String::new(),
+            properties: String::new(),
+            immediate_types: String::new(),
+            address_kinds: String::new(),
+            displacements: 0,
+            displacement_widths: String::new(),
+        };
+    }
+
+    fn errors(rows: Vec<Row>) -> Vec<String> {
+        let rows: Vec<(u64, Row)> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| (index as u64 + 2, row))
+            .collect();
+
+        return match validate_rows(&rows, 0..=0xff) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        };
+    }
+
+    #[test]
+    pub fn test_valid_rows() {
+        assert!(errors(vec![row(0, "nop", 0, ""), row(1, "push", 1, "r")]).is_empty());
+    }
+
+    #[test]
+    pub fn test_duplicates() {
+        assert_eq!(
+            errors(vec![
+                row(0, "nop", 0, ""),
+                row(1, "push", 1, "r"),
+                row(1, "pop", 1, "r"),
+                row(2, "nop", 0, ""),
+            ]),
+            vec![
+                "Row 4: Duplicate opcode 1, first used on row 3",
+                "Row 5: Duplicate opcode name nop, first used on row 2",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_number_columns() {
+        let mut wrong = row(1, "push", 1, "r");
+        let mut short = row(2, "pop", 1, "r");
+
+        wrong.binary = String::from("00000011");
+        short.hex = String::from("2");
+
+        assert_eq!(
+            errors(vec![row(0, "nop", 0, ""), wrong, short]),
+            vec![
+                "Row 3: Binary column 00000011 is 3 but the decimal column is 1",
+                "Row 4: Hex column 2 should be 2 digits long",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_ordering() {
+        assert_eq!(
+            errors(vec![row(0, "copy", 2, "r"), row(1, "jmp", 0, "x")]),
+            vec![
+                "Row 2: 2 Register operands declared but the friendly ordering \"r\" has 1",
+                "Row 3: Unknown operand x in the friendly ordering",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_gap() {
+        assert_eq!(
+            errors(vec![row(0, "nop", 0, ""), row(3, "halt", 0, "")]),
+            vec!["Row 3: Gap in numbering, opcodes 1 to 2 are missing"]
+        );
+    }
+
+    #[test]
+    pub fn test_widths() {
+        let mut jmp = row(0, "jmp24", 0, "d");
+
+        jmp.displacements = 1;
+        jmp.displacement_widths = String::from("24");
+
+        assert_eq!(
+            errors(vec![jmp]),
+            vec!["Row 2: Invalid width 24 in the Displacement Widths column, expected one of [8, 16, 32]"]
+        );
+    }
+}
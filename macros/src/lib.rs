@@ -0,0 +1,57 @@
+#![allow(clippy::needless_return)]
+
+use proc_macro::{Literal, TokenStream};
+use std::env;
+use std::path::PathBuf;
+use vxl_iset_gen::Generator;
+
+/// Generates an `instruction` and an `execute_instruction` module for an instruction set table, the
+/// path of the table is relative to the root of the crate using the macro. The table follows the
+/// format of `base_instruction_set.csv` and the generated code reuses the operand types of
+/// `vxl_iset`.
+///
+/// ```ignore
+/// vxl_iset::vxl_isa!("research_set.csv");
+///
+/// let instruction = instruction::Instruction::Nop;
+/// ```
+#[proc_macro]
+pub fn vxl_isa(input: TokenStream) -> TokenStream {
+    let relative_path = match syn::parse::<syn::LitStr>(input) {
+        Ok(path) => path.value(),
+        Err(e) => {
+            return compile_error(&format!(
+                "Expected the path of an instruction set table. Error: {}",
+                e
+            ))
+        }
+    };
+
+    let path = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(directory) => PathBuf::from(directory).join(relative_path),
+        None => PathBuf::from(relative_path),
+    };
+
+    let generator = match Generator::from_path(&path) {
+        Ok(generator) => generator,
+        Err(e) => return compile_error(&e.to_string()),
+    };
+
+    // Including the table makes the compiler rebuild the crate when the table changes.
+    let code = format!(
+        "pub mod instruction {{\nconst _: &[u8] = include_bytes!({});\n{}\n}}\npub mod execute_instruction {{\n{}\n}}",
+        Literal::string(&path.to_string_lossy()),
+        generator.instruction_code(),
+        generator.execute_code()
+    );
+
+    return code.parse().unwrap_or_else(|e| {
+        compile_error(&format!("Failed to parse the generated code. Error: {}", e))
+    });
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    return format!("compile_error!({});", Literal::string(message))
+        .parse()
+        .unwrap();
+}
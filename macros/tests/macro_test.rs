@@ -0,0 +1,52 @@
+use vxl_iset::instruction_arguments::{Immediate, Register, RegisterSet};
+
+vxl_iset_macros::vxl_isa!("tests/research_set.csv");
+
+use instruction::{Instruction, INSTRUCTIONS};
+
+#[test]
+fn custom_instruction_round_trip() {
+    let instruction = Instruction::Mac(Register::R0, Register::R1, Register::R2);
+    let bytes: Vec<u8> = instruction.into();
+
+    assert_eq!(bytes, vec![0x3, 0b0110_0111, 0b1000_0000]);
+    assert_eq!(Instruction::from_bytes(&bytes), Some((instruction, 3)));
+    assert_eq!(instruction.into_assembly(), "mac $r0, $r1, $r2");
+    assert_eq!(
+        instruction.reads(),
+        RegisterSet::new()
+            .with(Register::R0)
+            .with(Register::R1)
+            .with(Register::R2)
+    );
+}
+
+#[test]
+fn custom_instruction_table() {
+    assert_eq!(INSTRUCTIONS.len(), 6);
+    assert_eq!(INSTRUCTIONS[2].mnemonic(), "ldi");
+    assert_eq!(
+        Instruction::Ldi(Immediate::from(4u64), Register::R3).into_assembly(),
+        "ldi $r3, 0u4"
+    );
+    assert!(Instruction::Halt.is_terminator());
+}
+
+#[test]
+fn escaped_opcode_round_trip() {
+    let instruction = Instruction::Wide(Immediate::from(1u64), Register::R0);
+    let bytes: Vec<u8> = instruction.into();
+
+    assert_eq!(bytes[..2], [0xff, 0x00]);
+    assert_eq!(bytes.len(), INSTRUCTIONS[5].encoded_len());
+    assert_eq!(Instruction::from_bytes(&bytes), Some((instruction, 11)));
+}
+
+mod escaped_path {
+    vxl_iset_macros::vxl_isa!("tests/research\x5fset.csv");
+
+    #[test]
+    fn escaped_path_is_unescaped() {
+        assert_eq!(instruction::INSTRUCTIONS.len(), super::INSTRUCTIONS.len());
+    }
+}
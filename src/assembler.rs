@@ -0,0 +1,860 @@
+use crate::debug_info::{LineRow, LineTable};
+use crate::encoding::InstructionEncoding;
+use crate::instruction::{Instruction, Opcode};
+use crate::instruction_arguments::{
+    Address, AddressKind, Condition, Displacement, Immediate, OperandKind, Register,
+};
+use crate::object::{Object, Relocation};
+use crate::opcode;
+use crate::symbol::{Symbol, SymbolBinding, SymbolKind, SymbolTable};
+use crate::vxl_file::{VXLBuilder, VXLFile};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The branches assembled as a relative branch when given a label, paired with their relative forms
+/// from the narrowest displacement to the widest.
+pub const RELATIVE_BRANCHES: [(Opcode, [Opcode; 3]); 8] = [
+    (Opcode::Jmp, [Opcode::Jmp8, Opcode::Jmp16, Opcode::Jmp32]),
+    (Opcode::Jeq, [Opcode::Jeq8, Opcode::Jeq16, Opcode::Jeq32]),
+    (Opcode::Jne, [Opcode::Jne8, Opcode::Jne16, Opcode::Jne32]),
+    (Opcode::Jge, [Opcode::Jge8, Opcode::Jge16, Opcode::Jge32]),
+    (Opcode::Jgt, [Opcode::Jgt8, Opcode::Jgt16, Opcode::Jgt32]),
+    (Opcode::Jle, [Opcode::Jle8, Opcode::Jle16, Opcode::Jle32]),
+    (Opcode::Jlt, [Opcode::Jlt8, Opcode::Jlt16, Opcode::Jlt32]),
+    (
+        Opcode::Call,
+        [Opcode::Call8, Opcode::Call16, Opcode::Call32],
+    ),
+];
+
+/// The error returned when a program cannot be assembled, identifying the line at fault.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AssembleError {
+    line: usize,
+    kind: AssembleErrorKind,
+}
+
+/// The reason a line could not be assembled.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AssembleErrorKind {
+    /// No instruction uses the mnemonic.
+    UnknownMnemonic(String),
+    /// The instruction was given the wrong number of operands.
+    OperandCount { expected: usize, found: usize },
+    /// An operand is not valid for its position.
+    InvalidOperand(String),
+    /// A label is used but never defined.
+    UnknownLabel(String),
+    /// A label is defined more than once.
+    DuplicateLabel(String),
+    /// The target of a relative branch, a label or a literal displacement, is too far away for the
+    /// width of its displacement.
+    DisplacementOutOfRange(String),
+    /// A directive is malformed.
+    InvalidDirective(String),
+}
+
+/// An operand as written in the source, labels are resolved once the program is laid out.
+#[derive(Clone, Debug)]
+enum Argument {
+    Register(Register),
+    Immediate(Immediate),
+    Address(Address),
+    Condition(Condition),
+    Displacement(Displacement),
+    Label(String),
+}
+
+/// An assembled program.
+struct Assembly {
+    bytes: Vec<u8>,
+    symbols: SymbolTable,
+    lines: LineTable,
+    relocations: Vec<Relocation>,
+}
+
+/// A single instruction of the source along with its line.
+#[derive(Clone, Debug)]
+struct Statement {
+    line: usize,
+    opcode: Opcode,
+    arguments: Vec<(OperandKind, Argument)>,
+    /// The relative forms of a branch to a label, the opcode is picked from these while relaxing.
+    relative_forms: Option<[Opcode; 3]>,
+}
+
+/// Assembles a program written in the syntax of `Instruction::into_assembly` into its bytes in the
+/// fixed encoding.
+///
+/// # Syntax
+/// Every line holds at most one instruction, optionally preceded by the definition of a label,
+/// e.g. `loop: addi $r0, $r0, $r1`. Everything following a `;` is a comment. Registers are written
+/// as `$r0`, immediates as `0u12`, `0i-3` or `0f1.5`, addresses as `0u12`, displacements as `0d-4`
+/// and conditions by their name, e.g. `ne`. A label can be given wherever an address or a
+/// displacement is expected.
+///
+/// A `.loc "main.vl" 12 5` directive on a line of its own records that the instructions following
+/// it were compiled from line 12, column 5 of `main.vl`, the column being optional. Directives only
+/// affect the line table built by `assemble_file`. `.global square` marks the label `square` as a
+/// global function other objects may call, see `assemble_object`.
+///
+/// # Branch relaxation
+/// `jmp`, the conditional jumps and `call` given a label are assembled as the relative branch with
+/// the narrowest displacement the distance to the label fits in, keeping the program position
+/// independent. Branches given an address such as `0u12` stay absolute and relative branches
+/// written with their width, e.g. `jmp16 loop`, keep it.
+pub fn assemble(source: &str) -> Result<Vec<u8>, AssembleError> {
+    return assemble_with(source, InstructionEncoding::Fixed);
+}
+
+/// Assembles a program like `assemble` into its bytes in an encoding.
+///
+/// In the compact encoding the size of an instruction depends on the addresses of the labels it
+/// refers to, so the program is laid out again until the offset of every label settles.
+pub fn assemble_with(
+    source: &str,
+    encoding: InstructionEncoding,
+) -> Result<Vec<u8>, AssembleError> {
+    return assemble_program(source, encoding, false).map(|assembly| assembly.bytes);
+}
+
+/// Assembles a program like `assemble_with` into a vxl file holding a code section followed by a
+/// symbol section and, if the program has `.loc` directives, a line table.
+///
+/// Every label becomes a symbol in the code section, the first section of the file. Labels called
+/// by `call` or named by `.global` are functions covering the instructions up to the next function
+/// or the end of the program, the others are labels. Only the labels named by `.global` are global.
+pub fn assemble_file(
+    source: &str,
+    encoding: InstructionEncoding,
+) -> Result<VXLFile, AssembleError> {
+    let assembly = assemble_program(source, encoding, false)?;
+    let builder = VXLBuilder::new()
+        .code("code", assembly.bytes, encoding)
+        .symbols("symbols", &assembly.symbols);
+
+    if assembly.lines.is_empty() {
+        return Ok(builder.build());
+    }
+
+    return Ok(builder.line_table(&assembly.lines).build());
+}
+
+/// Assembles a module of a program into a relocatable object for `linker::link`, in the fixed
+/// encoding.
+///
+/// Labels become symbols as in `assemble_file`, only those named by `.global` being visible to other
+/// objects. A label the module does not define may be given wherever an address is expected, so `call print`
+/// assembles to an absolute `call` the linker patches with the address of the function `print` of
+/// another object. Every address given as a label has a relocation.
+pub fn assemble_object(source: &str) -> Result<Object, AssembleError> {
+    let assembly = assemble_program(source, InstructionEncoding::Fixed, true)?;
+
+    return Ok(Object::new(
+        assembly.bytes,
+        assembly.symbols,
+        assembly.relocations,
+        assembly.lines,
+    ));
+}
+
+/// Assembles a program into its bytes along with a symbol for every label. Labels the program does
+/// not define are allowed as addresses if `external` is set.
+fn assemble_program(
+    source: &str,
+    encoding: InstructionEncoding,
+    external: bool,
+) -> Result<Assembly, AssembleError> {
+    let mut statements = Vec::new();
+    let mut labels = BTreeMap::new();
+    let mut locations = Vec::new();
+    let mut globals = Vec::new();
+
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = text.split(';').next().unwrap_or("").trim();
+
+        if let Some(rest) = text.strip_prefix(".loc") {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                locations.push((statements.len(), parse_location(line, text, rest)?));
+
+                continue;
+            }
+        }
+
+        if let Some(rest) = text.strip_prefix(".global") {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                if !is_label(rest.trim()) {
+                    return Err(AssembleError::new(
+                        line,
+                        AssembleErrorKind::InvalidDirective(text.to_string()),
+                    ));
+                }
+
+                globals.push((line, rest.trim()));
+
+                continue;
+            }
+        }
+
+        if let Some((label, rest)) = text.split_once(':') {
+            let label = label.trim();
+
+            if !is_label(label) {
+                return Err(AssembleError::new(
+                    line,
+                    AssembleErrorKind::InvalidOperand(label.to_string()),
+                ));
+            }
+
+            if labels.insert(label.to_string(), statements.len()).is_some() {
+                return Err(AssembleError::new(
+                    line,
+                    AssembleErrorKind::DuplicateLabel(label.to_string()),
+                ));
+            }
+
+            text = rest.trim();
+        }
+
+        if !text.is_empty() {
+            statements.push(parse_statement(line, text)?);
+        }
+    }
+
+    for (line, global) in &globals {
+        if !labels.contains_key(*global) {
+            return Err(AssembleError::new(
+                *line,
+                AssembleErrorKind::UnknownLabel(global.to_string()),
+            ));
+        }
+    }
+
+    for statement in statements.iter_mut() {
+        let undefined = statement
+            .arguments
+            .iter()
+            .find_map(|(_, argument)| match argument {
+                Argument::Label(label) if !labels.contains_key(label) => Some(label.clone()),
+                _ => None,
+            });
+        let Some(label) = undefined else {
+            continue;
+        };
+        let absolute = RELATIVE_BRANCHES
+            .iter()
+            .find(|(_, forms)| Some(*forms) == statement.relative_forms);
+        let addresses_only = statement.arguments.iter().all(|(kind, argument)| {
+            *kind == OperandKind::Address || !matches!(argument, Argument::Label(_))
+        });
+
+        match absolute {
+            // Branches to other objects stay absolute so the linker can patch them.
+            Some((opcode, _)) if external => {
+                statement.opcode = *opcode;
+                statement.arguments[0].0 = OperandKind::Address;
+                statement.relative_forms = None;
+            }
+            _ if external && addresses_only => {}
+            _ => {
+                return Err(AssembleError::new(
+                    statement.line,
+                    AssembleErrorKind::UnknownLabel(label),
+                ));
+            }
+        }
+    }
+
+    let offsets = relax(&mut statements, &labels, encoding)?;
+    let mut bytes = Vec::new();
+
+    for (index, statement) in statements.iter().enumerate() {
+        let instruction = resolve(statement, offsets[index + 1], &offsets, &labels)?;
+
+        let _ = encoding.encode_to(&instruction, &mut bytes);
+    }
+
+    let mut lines = LineTable::new();
+
+    for (index, (file, line, column)) in locations {
+        let file = lines.file_index(file);
+
+        lines.insert(LineRow::new(offsets[index], file, line, column));
+    }
+
+    let mut relocations = Vec::new();
+
+    for (statement, offset) in statements.iter().zip(&offsets) {
+        let addresses = statement
+            .arguments
+            .iter()
+            .filter(|(kind, _)| *kind == OperandKind::Address);
+
+        for (index, (_, argument)) in addresses.enumerate() {
+            if let Argument::Label(label) = argument {
+                relocations.push(Relocation::new(*offset, index as u8, label).ok_or_else(
+                    || {
+                        AssembleError::new(
+                            statement.line,
+                            AssembleErrorKind::InvalidOperand(label.clone()),
+                        )
+                    },
+                )?);
+            }
+        }
+    }
+
+    return Ok(Assembly {
+        bytes,
+        symbols: symbols(&statements, &labels, &globals, &offsets),
+        lines,
+        relocations,
+    });
+}
+
+/// Parses the operands of a `.loc` directive, a quoted file name, a line and an optional column.
+fn parse_location<'a>(
+    line: usize,
+    text: &str,
+    operands: &'a str,
+) -> Result<(&'a str, u32, u32), AssembleError> {
+    let error = || AssembleError::new(line, AssembleErrorKind::InvalidDirective(text.to_string()));
+    let (file, rest) = operands
+        .trim()
+        .strip_prefix('"')
+        .and_then(|rest| rest.split_once('"'))
+        .ok_or_else(error)?;
+    let numbers = rest
+        .split_whitespace()
+        .map(|number| number.parse::<u32>().map_err(|_| error()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    return match numbers[..] {
+        [source_line] => Ok((file, source_line, 0)),
+        [source_line, column] => Ok((file, source_line, column)),
+        _ => Err(error()),
+    };
+}
+
+/// Builds the symbols of the labels of a laid out program, called and global labels being
+/// functions. Only global labels are given the global binding.
+fn symbols(
+    statements: &[Statement],
+    labels: &BTreeMap<String, usize>,
+    globals: &[(usize, &str)],
+    offsets: &[u64],
+) -> SymbolTable {
+    let called = statements
+        .iter()
+        .filter(|statement| {
+            matches!(
+                statement.opcode,
+                Opcode::Call | Opcode::Call8 | Opcode::Call16 | Opcode::Call32
+            )
+        })
+        .flat_map(|statement| &statement.arguments)
+        .filter_map(|(_, argument)| match argument {
+            Argument::Label(label) => Some(label.as_str()),
+            _ => None,
+        })
+        .chain(globals.iter().map(|(_, global)| *global))
+        .collect::<BTreeSet<_>>();
+    let mut starts = called
+        .iter()
+        .filter_map(|label| labels.get(*label))
+        .map(|index| offsets[*index])
+        .collect::<Vec<_>>();
+
+    starts.push(offsets[offsets.len() - 1]);
+    starts.sort_unstable();
+
+    let symbols = labels
+        .iter()
+        .filter_map(|(label, index)| {
+            let offset = offsets[*index];
+
+            if !called.contains(label.as_str()) {
+                return Symbol::new(label, SymbolKind::Label, 0, offset, 0);
+            }
+
+            let end = starts
+                .iter()
+                .find(|start| **start > offset)
+                .unwrap_or(&offset);
+            let binding = if globals.iter().any(|(_, global)| global == label) {
+                SymbolBinding::Global
+            } else {
+                SymbolBinding::Local
+            };
+
+            return Symbol::new(label, SymbolKind::Function, 0, offset, end - offset)
+                .map(|symbol| symbol.with_binding(binding));
+        })
+        .collect();
+
+    return SymbolTable::new(symbols);
+}
+
+/// Builds the instruction of a statement ending at `end`, with its labels resolved from the offsets
+/// of the statements.
+fn resolve(
+    statement: &Statement,
+    end: u64,
+    offsets: &[u64],
+    labels: &BTreeMap<String, usize>,
+) -> Result<Instruction, AssembleError> {
+    let mut immediates = Vec::new();
+    let mut conditions = Vec::new();
+    let mut registers = Vec::new();
+    let mut addresses = Vec::new();
+    let mut displacements = Vec::new();
+
+    for (kind, argument) in &statement.arguments {
+        match (kind, argument) {
+            (_, Argument::Register(register)) => registers.push(*register),
+            (_, Argument::Immediate(immediate)) => immediates.push(*immediate),
+            (_, Argument::Address(address)) => addresses.push(*address),
+            (_, Argument::Condition(condition)) => conditions.push(*condition),
+            (_, Argument::Displacement(displacement)) => {
+                if displacement.min_bytes() > encoded_width(statement.opcode) {
+                    return Err(AssembleError::new(
+                        statement.line,
+                        AssembleErrorKind::DisplacementOutOfRange(displacement.to_string()),
+                    ));
+                }
+
+                displacements.push(*displacement);
+            }
+            // Labels defined by other objects are patched by the linker.
+            (OperandKind::Address, Argument::Label(label)) => addresses.push(Address::code(
+                labels.get(label).map_or(0, |index| offsets[*index]),
+            )),
+            (_, Argument::Label(label)) => {
+                let displacement = Displacement::between(end, offsets[labels[label]])
+                    .filter(|displacement| {
+                        displacement.min_bytes() <= encoded_width(statement.opcode)
+                    })
+                    .ok_or_else(|| {
+                        AssembleError::new(
+                            statement.line,
+                            AssembleErrorKind::DisplacementOutOfRange(label.clone()),
+                        )
+                    })?;
+
+                displacements.push(displacement);
+            }
+        }
+    }
+
+    // The operands were checked against the opcode while parsing.
+    return Ok(Instruction::new(
+        statement.opcode,
+        immediates,
+        conditions,
+        registers,
+        addresses,
+        displacements,
+    )
+    .unwrap());
+}
+
+/// Lays out the program, widening relative branches to labels until every displacement fits.
+/// Labels start at offset 0 and both the offsets of labels and the widths of branches only ever
+/// grow, so the layout settles. Returns the offset of every statement followed by the size of the
+/// program.
+fn relax(
+    statements: &mut [Statement],
+    labels: &BTreeMap<String, usize>,
+    encoding: InstructionEncoding,
+) -> Result<Vec<u64>, AssembleError> {
+    let mut offsets = vec![0; statements.len() + 1];
+
+    loop {
+        for (index, statement) in statements.iter_mut().enumerate() {
+            let Some(forms) = statement.relative_forms else {
+                continue;
+            };
+            let target = statement
+                .arguments
+                .iter()
+                .find_map(|(_, argument)| match argument {
+                    Argument::Label(label) => Some(offsets[labels[label]]),
+                    _ => None,
+                });
+            let required = target
+                .and_then(|target| Displacement::between(offsets[index + 1], target))
+                .map_or(usize::MAX, |displacement| displacement.min_bytes());
+
+            if required > encoded_width(statement.opcode) {
+                // Targets too far for the widest form are reported once the program is encoded.
+                let form = forms
+                    .iter()
+                    .find(|form| encoded_width(**form) >= required)
+                    .unwrap_or(&forms[forms.len() - 1]);
+
+                statement.opcode = *form;
+            }
+        }
+
+        let mut next = Vec::with_capacity(statements.len() + 1);
+        let mut offset = 0;
+
+        for (index, statement) in statements.iter().enumerate() {
+            next.push(offset);
+
+            let instruction = resolve(statement, offsets[index + 1], &offsets, labels)?;
+
+            offset += encoding.encoded_len(&instruction) as u64;
+        }
+
+        next.push(offset);
+
+        if next == offsets {
+            return Ok(offsets);
+        }
+
+        offsets = next;
+    }
+}
+
+/// The number of bytes the displacement of a relative branch is encoded in, relative branches have
+/// no other operands.
+fn encoded_width(opcode: Opcode) -> usize {
+    return opcode.encoded_len() - opcode::encoded_len(opcode.value());
+}
+
+fn parse_statement(line: usize, text: &str) -> Result<Statement, AssembleError> {
+    let (mnemonic, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let opcode = mnemonic.parse::<Opcode>().map_err(|_| {
+        AssembleError::new(
+            line,
+            AssembleErrorKind::UnknownMnemonic(mnemonic.to_string()),
+        )
+    })?;
+    let kinds = opcode.info().friendly_ordering();
+    let operands = rest
+        .split(',')
+        .map(str::trim)
+        .filter(|operand| !operand.is_empty())
+        .collect::<Vec<_>>();
+
+    if operands.len() != kinds.len() {
+        return Err(AssembleError::new(
+            line,
+            AssembleErrorKind::OperandCount {
+                expected: kinds.len(),
+                found: operands.len(),
+            },
+        ));
+    }
+
+    let arguments = kinds
+        .iter()
+        .zip(operands)
+        .map(|(kind, operand)| {
+            let argument = parse_operand(*kind, operand).ok_or_else(|| {
+                AssembleError::new(line, AssembleErrorKind::InvalidOperand(operand.to_string()))
+            })?;
+
+            return Ok((*kind, argument));
+        })
+        .collect::<Result<Vec<_>, AssembleError>>()?;
+
+    // Only code addresses can be given as labels, the others hold handles known when running.
+    let heap_label = arguments
+        .iter()
+        .filter(|(kind, _)| *kind == OperandKind::Address)
+        .enumerate()
+        .find_map(|(index, (_, argument))| match argument {
+            Argument::Label(label) if opcode.address_kind(index) != Some(AddressKind::Code) => {
+                Some(label)
+            }
+            _ => None,
+        });
+
+    if let Some(label) = heap_label {
+        return Err(AssembleError::new(
+            line,
+            AssembleErrorKind::InvalidOperand(label.clone()),
+        ));
+    }
+
+    let mut statement = Statement {
+        line,
+        opcode,
+        arguments,
+        relative_forms: None,
+    };
+
+    if let [(OperandKind::Address, Argument::Label(_))] = statement.arguments[..] {
+        if let Some((_, forms)) = RELATIVE_BRANCHES
+            .iter()
+            .find(|(branch, _)| *branch == opcode)
+        {
+            statement.opcode = forms[0];
+            statement.arguments[0].0 = OperandKind::Displacement;
+            statement.relative_forms = Some(*forms);
+        }
+    }
+
+    return Ok(statement);
+}
+
+fn parse_operand(kind: OperandKind, operand: &str) -> Option<Argument> {
+    return match kind {
+        OperandKind::Register => (0..16)
+            .map(Register::from_bits)
+            .find(|register| register.to_string() == operand)
+            .map(Argument::Register),
+        OperandKind::Condition => (0..=Condition::GEU as u8)
+            .map(Condition::from_bits)
+            .find(|condition| condition.to_string() == operand)
+            .map(Argument::Condition),
+        OperandKind::Immediate => parse_immediate(operand).map(Argument::Immediate),
+        OperandKind::Address if is_label(operand) => Some(Argument::Label(operand.to_string())),
+        OperandKind::Address => match parse_immediate(operand)? {
+            immediate if operand.starts_with("0u") => {
+                Some(Argument::Address(Address::code(immediate.as_u64())))
+            }
+            _ => None,
+        },
+        OperandKind::Displacement if is_label(operand) => {
+            Some(Argument::Label(operand.to_string()))
+        }
+        OperandKind::Displacement => Some(Argument::Displacement(Displacement::new(
+            operand.strip_prefix("0d")?.parse().ok()?,
+        ))),
+    };
+}
+
+fn parse_immediate(operand: &str) -> Option<Immediate> {
+    let (prefix, value) = (operand.get(..2)?, operand.get(2..)?);
+
+    return match prefix {
+        "0u" => Some(Immediate::from(value.parse::<u64>().ok()?)),
+        "0i" => Some(Immediate::from(value.parse::<i64>().ok()?)),
+        "0f" => Some(Immediate::from(value.parse::<f64>().ok()?)),
+        _ => None,
+    };
+}
+
+/// True if a name can be used as a label, labels start with a letter, `_` or `.` followed by any
+/// number of letters, digits, `_` or `.`.
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    // Labels become symbols so their names are limited in the same way.
+    return name.len() <= Symbol::MAX_NAME_LEN
+        && matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '.')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+}
+
+impl AssembleError {
+    pub const fn new(line: usize, kind: AssembleErrorKind) -> Self {
+        return Self { line, kind };
+    }
+
+    /// The one based line of the source at fault.
+    pub const fn line(&self) -> usize {
+        return self.line;
+    }
+
+    pub const fn kind(&self) -> &AssembleErrorKind {
+        return &self.kind;
+    }
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "line {}: {}", self.line, self.kind);
+    }
+}
+
+impl fmt::Display for AssembleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            AssembleErrorKind::UnknownMnemonic(mnemonic) => {
+                write!(f, "unknown mnemonic {}", mnemonic)
+            }
+            AssembleErrorKind::OperandCount { expected, found } => {
+                write!(f, "expected {} operands but found {}", expected, found)
+            }
+            AssembleErrorKind::InvalidOperand(operand) => write!(f, "invalid operand {}", operand),
+            AssembleErrorKind::UnknownLabel(label) => write!(f, "unknown label {}", label),
+            AssembleErrorKind::DuplicateLabel(label) => {
+                write!(f, "label {} is already defined", label)
+            }
+            AssembleErrorKind::DisplacementOutOfRange(target) => {
+                write!(f, "{} is too far away for the displacement", target)
+            }
+            AssembleErrorKind::InvalidDirective(directive) => {
+                write!(f, "invalid directive {}", directive)
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug_info::backtrace;
+    use crate::program::Program;
+    use alloc::format;
+
+    #[test]
+    pub fn test_assemble() {
+        let source =
+            "start: ldi $r0, 0i-3 ; a comment\n\ncmpi $r0, $r1\njne start\nfreea 0u7\njmp 0u0\n";
+        let program = Program::predecode(&assemble(source).unwrap()).unwrap();
+
+        assert_eq!(
+            program.instructions(),
+            &[
+                Instruction::Ldi(Immediate::from(-3i64), Register::R0),
+                Instruction::Cmpi(Register::R0, Register::R1),
+                Instruction::Jne8(Displacement::new(-15)),
+                Instruction::Freea(Address::heap(7)),
+                Instruction::Jmp(Address::code(0)),
+            ]
+        );
+        assert_eq!(program.target(2), Some(0));
+    }
+
+    #[test]
+    pub fn test_relaxation() {
+        let padding = "ldi $r0, 0i0\n".repeat(12) + "nop\n";
+        let source = format!(
+            "jmp end\ncall end\njmp16 end\n{}end: jmp end\njmp far\n{}far: ret",
+            padding,
+            "ldi $r0, 0i0\n".repeat(3300)
+        );
+        let program = Program::predecode(&assemble(&source).unwrap()).unwrap();
+        let opcodes = program
+            .instructions()
+            .iter()
+            .map(Instruction::opcode)
+            .collect::<Vec<_>>();
+
+        // The first branch is just too far for 8 bits while the call, 3 bytes closer, is not.
+        assert_eq!(
+            &opcodes[..3],
+            &[Opcode::Jmp16, Opcode::Call8, Opcode::Jmp16]
+        );
+        assert_eq!(opcodes[16], Opcode::Jmp8);
+        assert_eq!(opcodes[17], Opcode::Jmp32);
+        assert_eq!(program.target(0), Some(16));
+        assert_eq!(program.target(17), Some(program.len() - 1));
+    }
+
+    #[test]
+    pub fn test_symbols() {
+        let source = "main: call square\ncall cube\nhalt\nsquare: muli $r0, $r0, $r0\nret\ncube: mov $r1, $r0\nloop: jne loop\nret\n";
+        let file = assemble_file(source, InstructionEncoding::Fixed).unwrap();
+        let symbols = file.symbols().unwrap();
+        let square = symbols.symbol_by_name("square").unwrap();
+        let cube = symbols.symbol_by_name("cube").unwrap();
+
+        assert_eq!(
+            symbols.symbol_by_name("main").unwrap().kind(),
+            SymbolKind::Label
+        );
+        assert_eq!(square.kind(), SymbolKind::Function);
+        assert_eq!(square.offset() + square.size(), cube.offset());
+        assert_eq!(
+            cube.offset() + cube.size(),
+            file.program().unwrap().offset_of(7).unwrap() + 1
+        );
+        assert_eq!(symbols.symbol_at(cube.offset() + 1), Some(cube));
+        assert_eq!(
+            symbols.symbol_at(symbols.symbol_by_name("loop").unwrap().offset()),
+            symbols.symbol_by_name("loop")
+        );
+    }
+
+    #[test]
+    pub fn test_locations() {
+        let source = ".loc \"main.vl\" 3\nmain: call square\nhalt\nsquare:\n.loc \"math.vl\" 7 9\nmuli $r0, $r0, $r0\n.loc \"math.vl\" 8 5 ; ret\nret\n";
+        let file = assemble_file(source, InstructionEncoding::Compact).unwrap();
+        let lines = file.line_table().unwrap();
+        let square = file
+            .symbols()
+            .unwrap()
+            .symbol_by_name("square")
+            .unwrap()
+            .offset();
+
+        assert_eq!(lines.files(), &["main.vl", "math.vl"]);
+        assert_eq!(lines.location(1).unwrap().to_string(), "main.vl:3");
+        assert_eq!(lines.location(square).unwrap().to_string(), "math.vl:7:9");
+        assert_eq!(
+            backtrace(&[square + 1, 0], &file.symbols().unwrap(), &lines),
+            "#0 0x5 in square at math.vl:7:9\n#1 0x0 in main at main.vl:3\n"
+        );
+        assert_eq!(
+            assemble_file("nop", InstructionEncoding::Fixed)
+                .unwrap()
+                .sections()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_errors() {
+        assert_eq!(
+            assemble("nop\nldi $r0"),
+            Err(AssembleError::new(
+                2,
+                AssembleErrorKind::OperandCount {
+                    expected: 2,
+                    found: 1
+                }
+            ))
+        );
+        assert_eq!(
+            assemble("jmp nowhere").unwrap_err().kind(),
+            &AssembleErrorKind::UnknownLabel("nowhere".to_string())
+        );
+        assert_eq!(assemble("a: nop\na: nop").unwrap_err().line(), 2);
+        assert_eq!(
+            assemble("nop\njmp8 0d300\n"),
+            Err(AssembleError::new(
+                2,
+                AssembleErrorKind::DisplacementOutOfRange("0d300".to_string())
+            ))
+        );
+        assert!(assemble("jmp16 0d70000").is_err());
+        assert!(assemble("jmp16 0d-32768").is_ok());
+        assert_eq!(
+            assemble(&format!(
+                "jmp8 end\n{}end: ret",
+                "ldi $r0, 0i0\n".repeat(13)
+            )),
+            Err(AssembleError::new(
+                1,
+                AssembleErrorKind::DisplacementOutOfRange("end".to_string())
+            ))
+        );
+        assert_eq!(
+            assemble("nop\n.loc main.vl 3").unwrap_err(),
+            AssembleError::new(
+                2,
+                AssembleErrorKind::InvalidDirective(".loc main.vl 3".to_string())
+            )
+        );
+        assert_eq!(assemble(".loc \"main.vl\" 3 4 5").unwrap_err().line(), 1);
+        assert_eq!(
+            assemble("ldi $r10, 0i0").unwrap_err().kind(),
+            &AssembleErrorKind::InvalidOperand("$r10".to_string())
+        );
+        assert_eq!(
+            assemble_object("freea foo\nfoo: halt").unwrap_err(),
+            AssembleError::new(1, AssembleErrorKind::InvalidOperand("foo".to_string()))
+        );
+    }
+}
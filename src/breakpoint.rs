@@ -0,0 +1,169 @@
+use crate::encoding::InstructionEncoding;
+use crate::instruction::{Instruction, Opcode};
+use alloc::collections::BTreeMap;
+
+/// Tracks the breakpoints a debugger has patched into the program bytes.
+///
+/// # Patching rules
+/// Instructions vary in length and the shortest is a single byte, so a debugger only ever
+/// overwrites the first byte of an instruction with the `brk` opcode, remembering the original
+/// byte by offset. This never touches the bytes of a neighbouring instruction. For instructions with
+/// a two byte opcode the escape prefix is the byte replaced.
+///
+/// A vm executing a `brk` traps to the host with the offset of the `brk` opcode and the code held
+/// in its immediate. For a patched breakpoint the bytes after the opcode still belong to the
+/// original instruction, so the code is meaningless and there may be too few bytes left in the
+/// program to hold one. `BreakpointSet::trap_code` reads the code in a
+/// way that handles both.
+///
+/// When a trap is reported at an offset in the set the debugger restores the original byte and
+/// resumes at the same offset, re-inserting the breakpoint after stepping over the instruction if
+/// it should remain. A trap at any other offset is a `brk` emitted by the compiler, its code is
+/// valid and execution resumes after the full `brk` instruction.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct BreakpointSet {
+    original_bytes: BTreeMap<usize, u8>,
+}
+
+impl BreakpointSet {
+    /// The opcode byte of the `brk` instruction, which is encoded in a single byte.
+    pub const OPCODE: u8 = Opcode::Brk.value() as u8;
+
+    /// Creates an empty set of breakpoints.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Patches a breakpoint over the instruction starting at offset. Returns false if the offset is
+    /// outside of the program or already has a breakpoint.
+    pub fn insert(&mut self, program: &mut [u8], offset: usize) -> bool {
+        if self.original_bytes.contains_key(&offset) || offset >= program.len() {
+            return false;
+        }
+
+        self.original_bytes.insert(offset, program[offset]);
+        program[offset] = Self::OPCODE;
+
+        return true;
+    }
+
+    /// Restores the original byte of the breakpoint at offset. Returns false if there is no
+    /// breakpoint at the offset.
+    pub fn remove(&mut self, program: &mut [u8], offset: usize) -> bool {
+        return match self.original_bytes.remove(&offset) {
+            Some(byte) if offset < program.len() => {
+                program[offset] = byte;
+
+                true
+            }
+            _ => false,
+        };
+    }
+
+    /// True if a trap at offset was caused by a breakpoint in this set rather than by a `brk`
+    /// emitted by the compiler.
+    pub fn contains(&self, offset: usize) -> bool {
+        return self.original_bytes.contains_key(&offset);
+    }
+
+    /// The byte that was replaced by the breakpoint at offset.
+    pub fn original_byte(&self, offset: usize) -> Option<u8> {
+        return self.original_bytes.get(&offset).copied();
+    }
+
+    /// Writes the original bytes back over every breakpoint without removing them from the set, for
+    /// example to disassemble a copy of a patched program.
+    pub fn restore_into(&self, program: &mut [u8]) {
+        for (offset, byte) in &self.original_bytes {
+            if let Some(target) = program.get_mut(*offset) {
+                *target = *byte;
+            }
+        }
+    }
+
+    /// Returns the code of the `brk` at offset in a program in an encoding, or None if there is no
+    /// `brk` opcode there. The code of a breakpoint in this set is always 0, as is the code of a
+    /// `brk` truncated by the end of the program.
+    pub fn trap_code(
+        &self,
+        program: &[u8],
+        offset: usize,
+        encoding: InstructionEncoding,
+    ) -> Option<u64> {
+        if *program.get(offset)? != Self::OPCODE {
+            return None;
+        }
+
+        if self.contains(offset) {
+            return Some(0);
+        }
+
+        return match encoding.decode(&program[offset..]) {
+            Some((Instruction::Brk(code), _)) => Some(code.as_u64()),
+            _ => Some(0),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction_arguments::{Immediate, Register};
+    use alloc::vec::Vec;
+
+    #[test]
+    pub fn test_insert_and_remove() {
+        let original: Vec<u8> = [Instruction::Ret, Instruction::Push(Register::R0)]
+            .into_iter()
+            .flat_map(Into::<Vec<u8>>::into)
+            .collect();
+        let mut program = original.clone();
+        let mut breakpoints = BreakpointSet::new();
+
+        assert!(breakpoints.insert(&mut program, 0));
+        assert!(!breakpoints.insert(&mut program, 0));
+        assert_eq!(program[0], BreakpointSet::OPCODE);
+        // The single byte ret is patched without touching the push that follows it.
+        assert_eq!(program[1..], original[1..]);
+        assert_eq!(
+            breakpoints.trap_code(&program, 0, InstructionEncoding::Fixed),
+            Some(0)
+        );
+
+        assert!(breakpoints.remove(&mut program, 0));
+        assert!(!breakpoints.remove(&mut program, 0));
+        assert_eq!(program, original);
+    }
+
+    #[test]
+    pub fn test_compiled_trap_code() {
+        let program: Vec<u8> = Instruction::Brk(Immediate::from(7u64)).into();
+        let breakpoints = BreakpointSet::new();
+
+        let fixed = InstructionEncoding::Fixed;
+
+        assert_eq!(breakpoints.trap_code(&program, 0, fixed), Some(7));
+        assert_eq!(breakpoints.trap_code(&program[..4], 0, fixed), Some(0));
+        assert_eq!(breakpoints.trap_code(&program, 1, fixed), None);
+    }
+
+    #[test]
+    pub fn test_compact_trap_code() {
+        let compact = InstructionEncoding::Compact;
+        let mut program = Vec::new();
+
+        for instruction in [
+            Instruction::Brk(Immediate::from(5u64)),
+            Instruction::Ldi(Immediate::from(-2i64), Register::R0),
+            Instruction::Halt,
+        ] {
+            let _ = compact.encode_to(&instruction, &mut program);
+        }
+
+        let breakpoints = BreakpointSet::new();
+
+        assert_eq!(program.len(), 2 + 3 + 1);
+        assert_eq!(breakpoints.trap_code(&program, 0, compact), Some(5));
+        assert_eq!(breakpoints.trap_code(&program[..1], 0, compact), Some(0));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::instruction_arguments::Displacement;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt;
+
+/// A destination for encoded instructions, similar to `std::io::Write` but usable without std.
+pub trait ByteSink {
+    type Error;
+
+    /// Writes all of the bytes to the sink.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// The error returned when encoding into a buffer that cannot hold the encoded bytes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BufferTooSmall {
+    required: usize,
+    available: usize,
+}
+
+/// The error returned when an instruction cannot be encoded into a sink.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EncodeError<E> {
+    /// The sink could not take the encoded bytes.
+    Sink(E),
+    /// A displacement does not fit in the width its instruction declares, e.g. `Jmp8` with a
+    /// displacement of 300.
+    DisplacementOutOfRange(Displacement),
+}
+
+/// Writes into a preallocated buffer, failing once the buffer is full.
+#[derive(Debug)]
+pub struct SliceSink<'a> {
+    buffer: &'a mut [u8],
+    position: usize,
+}
+
+impl BufferTooSmall {
+    pub const fn new(required: usize, available: usize) -> Self {
+        return Self {
+            required,
+            available,
+        };
+    }
+
+    /// The number of bytes that would have been written.
+    pub const fn required(&self) -> usize {
+        return self.required;
+    }
+
+    /// The number of bytes that were left in the buffer.
+    pub const fn available(&self) -> usize {
+        return self.available;
+    }
+}
+
+impl<'a> SliceSink<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        return Self {
+            buffer,
+            position: 0,
+        };
+    }
+
+    /// The number of bytes written so far.
+    pub const fn position(&self) -> usize {
+        return self.position;
+    }
+
+    /// Returns the bytes written so far.
+    pub fn into_written(self) -> &'a mut [u8] {
+        return &mut self.buffer[..self.position];
+    }
+}
+
+impl<E> From<E> for EncodeError<E> {
+    fn from(e: E) -> Self {
+        return EncodeError::Sink(e);
+    }
+}
+
+impl ByteSink for Vec<u8> {
+    type Error = Infallible;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(bytes);
+
+        return Ok(());
+    }
+}
+
+impl ByteSink for SliceSink<'_> {
+    type Error = BufferTooSmall;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        let remaining = &mut self.buffer[self.position..];
+
+        if remaining.len() < bytes.len() {
+            return Err(BufferTooSmall::new(bytes.len(), remaining.len()));
+        }
+
+        remaining[..bytes.len()].copy_from_slice(bytes);
+        self.position += bytes.len();
+
+        return Ok(());
+    }
+}
+
+impl<S: ByteSink + ?Sized> ByteSink for &mut S {
+    type Error = S::Error;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        return (**self).write_bytes(bytes);
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "buffer too small, {} bytes required but {} available",
+            self.required, self.available
+        );
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for EncodeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            EncodeError::Sink(e) => write!(f, "{}", e),
+            EncodeError::DisplacementOutOfRange(displacement) => write!(
+                f,
+                "displacement {} does not fit in the width of the instruction",
+                displacement
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{Instruction, Opcode};
+    use crate::instruction_arguments::{Address, Immediate, Register};
+    use alloc::vec;
+
+    #[test]
+    pub fn test_encode_into() {
+        let instructions = [
+            Instruction::Nop,
+            Instruction::Ldi(Immediate::from(-12i64), Register::R3),
+            Instruction::Jmp(Address::from(0x40u64)),
+            Instruction::Brk(Immediate::from(3u64)),
+        ];
+        let mut buffer = [0; 64];
+        let mut sink = SliceSink::new(&mut buffer);
+        let mut expected = Vec::new();
+
+        for instruction in instructions {
+            let bytes: Vec<u8> = instruction.into();
+
+            assert_eq!(
+                instruction.encode_to(&mut sink),
+                Ok(instruction.encoded_len())
+            );
+            assert!(instruction.encoded_len() <= Opcode::MAX_ENCODED_LEN);
+            expected.extend(bytes);
+        }
+
+        assert_eq!(sink.into_written(), &expected[..]);
+    }
+
+    #[test]
+    pub fn test_buffer_too_small() {
+        let instruction = Instruction::Jmp(Address::from(0x40u64));
+        let mut buffer = [0; 4];
+
+        assert_eq!(
+            instruction.encode_into(&mut buffer),
+            Err(EncodeError::Sink(BufferTooSmall::new(9, 4)))
+        );
+        assert_eq!(
+            instruction.encode_to(&mut SliceSink::new(&mut buffer)),
+            Err(EncodeError::Sink(BufferTooSmall::new(9, 4)))
+        );
+
+        let mut bytes = vec![0x1];
+
+        assert_eq!(instruction.encode_to(&mut bytes), Ok(9));
+        assert_eq!(bytes.len(), 10);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::encoding::{read_compact, write_compact, CompactForm, MAX_COMPACT_VALUE_LEN};
+use crate::symbol::SymbolTable;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Maps the offsets of instructions to the place in the source they were compiled from.
+///
+/// A row gives the location of the instructions from its offset up to the offset of the next row,
+/// the rows are kept ordered by their offset.
+///
+/// # Representation when in binary
+/// Every number is written in the compact encoding, see `encoding::write_compact`.
+///
+/// The number of files, followed by the length in bytes and the UTF-8 name of each file.
+///
+/// The number of rows, followed by each row as the difference between its offset and that of the
+/// previous row, the index of its file, the difference between its line and that of the previous
+/// row (signed) and its column.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct LineTable {
+    files: Vec<String>,
+    rows: Vec<LineRow>,
+}
+
+/// A row of a line table.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct LineRow {
+    offset: u64,
+    file: u32,
+    line: u32,
+    column: u32,
+}
+
+/// A place in the source, lines and columns count from 1 with 0 meaning unknown.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct SourceLocation<'a> {
+    pub file: &'a str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl LineTable {
+    /// The name of the debug section holding the line table of a vxl file.
+    pub const SECTION_NAME: &'static str = "debug_line";
+
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// The index of a file, adding it if the table does not refer to it yet.
+    pub fn file_index(&mut self, file: &str) -> u32 {
+        return match self.files.iter().position(|name| name == file) {
+            Some(index) => index as u32,
+            None => {
+                self.files.push(String::from(file));
+
+                (self.files.len() - 1) as u32
+            }
+        };
+    }
+
+    /// Sets the location of the instructions from an offset, replacing the row already starting
+    /// there. Returns false if the file is not in the table.
+    pub fn insert(&mut self, row: LineRow) -> bool {
+        if row.file as usize >= self.files.len() {
+            return false;
+        }
+
+        match self
+            .rows
+            .binary_search_by_key(&row.offset, |row| row.offset)
+        {
+            Ok(index) => self.rows[index] = row,
+            Err(index) => self.rows.insert(index, row),
+        }
+
+        return true;
+    }
+
+    pub fn files(&self) -> &[String] {
+        return &self.files;
+    }
+
+    pub fn rows(&self) -> &[LineRow] {
+        return &self.rows;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.rows.is_empty();
+    }
+
+    /// The row covering an offset, None if the offset precedes every row.
+    pub fn row_at(&self, offset: u64) -> Option<LineRow> {
+        let index = self.rows.partition_point(|row| row.offset <= offset);
+
+        return index.checked_sub(1).map(|index| self.rows[index]);
+    }
+
+    /// The place in the source the instruction at an offset was compiled from.
+    pub fn location(&self, offset: u64) -> Option<SourceLocation<'_>> {
+        return self.row_at(offset).map(|row| self.location_of(row));
+    }
+
+    /// The location a row gives.
+    pub fn location_of(&self, row: LineRow) -> SourceLocation<'_> {
+        return SourceLocation {
+            file: &self.files[row.file as usize],
+            line: row.line,
+            column: row.column,
+        };
+    }
+
+    /// Reads the contents of a line table section, returns None if they are truncated or invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        let mut table = Self::new();
+
+        for _ in 0..reader.read(CompactForm::Unsigned)? {
+            let len = reader.read(CompactForm::Unsigned)? as usize;
+            let name = bytes.get(reader.offset..reader.offset.checked_add(len)?)?;
+
+            table
+                .files
+                .push(String::from(core::str::from_utf8(name).ok()?));
+            reader.offset += len;
+        }
+
+        let mut offset = 0u64;
+        let mut line = 0u32;
+
+        for _ in 0..reader.read(CompactForm::Unsigned)? {
+            offset = offset.checked_add(reader.read(CompactForm::Unsigned)?)?;
+
+            let file = u32::try_from(reader.read(CompactForm::Unsigned)?).ok()?;
+
+            line = u32::try_from(line as i64 + reader.read(CompactForm::Signed)? as i64).ok()?;
+
+            let column = u32::try_from(reader.read(CompactForm::Unsigned)?).ok()?;
+
+            if file as usize >= table.files.len() {
+                return None;
+            }
+
+            table.rows.push(LineRow::new(offset, file, line, column));
+        }
+
+        return Some(table);
+    }
+}
+
+impl LineRow {
+    pub const fn new(offset: u64, file: u32, line: u32, column: u32) -> Self {
+        return Self {
+            offset,
+            file,
+            line,
+            column,
+        };
+    }
+
+    /// The offset of the first instruction the row covers.
+    pub const fn offset(&self) -> u64 {
+        return self.offset;
+    }
+
+    /// The index of the file in `LineTable::files`.
+    pub const fn file(&self) -> u32 {
+        return self.file;
+    }
+
+    pub const fn line(&self) -> u32 {
+        return self.line;
+    }
+
+    pub const fn column(&self) -> u32 {
+        return self.column;
+    }
+}
+
+/// Reads the compact numbers of a line table in turn.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn read(&mut self, form: CompactForm) -> Option<u64> {
+        let (value, len) = read_compact(self.bytes.get(self.offset..)?, form)?;
+
+        self.offset += len;
+
+        return Some(value);
+    }
+}
+
+/// Formats a backtrace, naming every frame after the function covering it, or failing that the
+/// label at it, and the place in the source it was compiled from.
+///
+/// Frames are the offsets of the instructions being executed, innermost first. The return offsets
+/// pushed by `call` point past the call, so `offset - 1` should be given for them to locate the
+/// call itself. Every frame is given a line such as `#1 0x1c in main at main.vl:4:9`, leaving out
+/// what is not known.
+pub fn backtrace(frames: &[u64], symbols: &SymbolTable, lines: &LineTable) -> String {
+    let mut output = String::new();
+
+    for (index, offset) in frames.iter().enumerate() {
+        output.push_str(&format!("#{} {:#x}", index, offset));
+
+        let symbol = symbols
+            .function_at(*offset)
+            .or_else(|| symbols.symbol_at(*offset));
+
+        if let Some(symbol) = symbol {
+            output.push_str(&format!(" in {}", symbol));
+        }
+
+        if let Some(location) = lines.location(*offset) {
+            output.push_str(&format!(" at {}", location));
+        }
+
+        output.push('\n');
+    }
+
+    return output;
+}
+
+impl Into<Vec<u8>> for &LineTable {
+    fn into(self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buffer = [0; MAX_COMPACT_VALUE_LEN];
+        let mut write = |bytes: &mut Vec<u8>, value: u64, form: CompactForm| {
+            let len = write_compact(value, form, &mut buffer);
+
+            bytes.extend_from_slice(&buffer[..len]);
+        };
+
+        write(&mut bytes, self.files.len() as u64, CompactForm::Unsigned);
+
+        for file in &self.files {
+            write(&mut bytes, file.len() as u64, CompactForm::Unsigned);
+            bytes.extend_from_slice(file.as_bytes());
+        }
+
+        write(&mut bytes, self.rows.len() as u64, CompactForm::Unsigned);
+
+        let mut previous = LineRow::new(0, 0, 0, 0);
+
+        for row in &self.rows {
+            let line = row.line as i64 - previous.line as i64;
+
+            write(
+                &mut bytes,
+                row.offset - previous.offset,
+                CompactForm::Unsigned,
+            );
+            write(&mut bytes, row.file as u64, CompactForm::Unsigned);
+            write(&mut bytes, line as u64, CompactForm::Signed);
+            write(&mut bytes, row.column as u64, CompactForm::Unsigned);
+            previous = *row;
+        }
+
+        return bytes;
+    }
+}
+
+impl fmt::Display for SourceLocation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match (self.line, self.column) {
+            (0, _) => write!(f, "{}", self.file),
+            (line, 0) => write!(f, "{}:{}", self.file, line),
+            (line, column) => write!(f, "{}:{}:{}", self.file, line, column),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::{Symbol, SymbolKind};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    pub fn test_line_table() {
+        let mut table = LineTable::new();
+        let main = table.file_index("main.vl");
+        let util = table.file_index("util.vl");
+
+        assert_eq!(table.file_index("main.vl"), main);
+        assert!(table.insert(LineRow::new(0, main, 3, 5)));
+        assert!(table.insert(LineRow::new(20, util, 1, 1)));
+        assert!(table.insert(LineRow::new(12, main, 4, 0)));
+        assert!(!table.insert(LineRow::new(30, 7, 1, 1)));
+
+        assert_eq!(table.location(0).unwrap().to_string(), "main.vl:3:5");
+        assert_eq!(table.location(19).unwrap().to_string(), "main.vl:4");
+        assert_eq!(table.location(400).unwrap().file, "util.vl");
+
+        let bytes: Vec<u8> = (&table).into();
+
+        // Two files, then 3 rows of 4 single byte numbers.
+        assert_eq!(bytes.len(), 1 + 8 + 8 + 1 + 12);
+        assert_eq!(LineTable::from_bytes(&bytes), Some(table.clone()));
+        assert_eq!(LineTable::from_bytes(&bytes[..bytes.len() - 1]), None);
+
+        let symbols = SymbolTable::new(vec![
+            Symbol::new("main", SymbolKind::Function, 0, 0, 20).unwrap(),
+            Symbol::new("helper", SymbolKind::Function, 0, 20, 10).unwrap(),
+        ]);
+
+        assert_eq!(
+            backtrace(&[22, 13, 40], &symbols, &table),
+            "#0 0x16 in helper at util.vl:1:1\n#1 0xd in main at main.vl:4\n#2 0x28 at util.vl:1:1\n"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::debug_info::LineTable;
+use crate::encoding::InstructionEncoding;
+use crate::instruction_arguments::Operand;
+use crate::program::{DecodeError, Program};
+use crate::section::SectionKind;
+use crate::symbol::SymbolTable;
+use crate::vxl_file::{LoadError, VXLFile};
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Disassembles a program into assembly that `assembler::assemble` reads back into the same bytes.
+///
+/// Every instruction a branch jumps to is preceded by a generated label named after its offset,
+/// e.g. `L12`. Relative branches refer to their target by its label so the absolute target is shown
+/// rather than the displacement, while absolute branches keep their address.
+pub fn disassemble(bytes: &[u8]) -> Result<String, DecodeError> {
+    return disassemble_with(bytes, InstructionEncoding::Fixed, &SymbolTable::default());
+}
+
+/// Disassembles a program in an encoding like `disassemble`, naming instructions after the code
+/// symbols starting at them rather than generating labels.
+pub fn disassemble_with(
+    bytes: &[u8],
+    encoding: InstructionEncoding,
+    symbols: &SymbolTable,
+) -> Result<String, DecodeError> {
+    return Ok(disassemble_program(
+        &Program::predecode_with(bytes, encoding)?,
+        symbols,
+        &LineTable::default(),
+    ));
+}
+
+/// Disassembles the program of a vxl file, naming instructions after the symbols of its code
+/// section and giving the rows of its line table as `.loc` directives.
+pub fn disassemble_file(file: &VXLFile) -> Result<String, LoadError> {
+    let section = file
+        .sections()?
+        .iter()
+        .position(|section| section.kind() == SectionKind::Code)
+        .ok_or(LoadError::MissingCode)?;
+    let symbols = file
+        .symbols()?
+        .symbols()
+        .iter()
+        .filter(|symbol| symbol.section() as usize == section)
+        .cloned()
+        .collect();
+
+    return Ok(disassemble_program(
+        &file.program()?,
+        &SymbolTable::new(symbols),
+        &file.line_table()?,
+    ));
+}
+
+fn disassemble_program(program: &Program, symbols: &SymbolTable, lines: &LineTable) -> String {
+    let targets = (0..program.len())
+        .filter_map(|index| program.target(index))
+        .collect::<BTreeSet<_>>();
+    let mut output = String::new();
+
+    for (index, instruction) in program.instructions().iter().enumerate() {
+        let offset = program.offset_of(index).unwrap_or(0);
+        let names = symbols
+            .symbols()
+            .iter()
+            .filter(|symbol| symbol.kind().is_code() && symbol.offset() == offset)
+            .map(|symbol| symbol.name().to_string())
+            .collect::<Vec<_>>();
+
+        if names.is_empty() && targets.contains(&index) {
+            output.push_str(&format!("{}:\n", label(program, symbols, index)));
+        }
+
+        for name in names {
+            output.push_str(&format!("{}:\n", name));
+        }
+
+        if let Some(row) = lines.row_at(offset).filter(|row| row.offset() == offset) {
+            let location = lines.location_of(row);
+
+            output.push_str(&format!(
+                "    .loc \"{}\" {} {}\n",
+                location.file, location.line, location.column
+            ));
+        }
+
+        let relative = instruction
+            .operands()
+            .any(|operand| matches!(operand, Operand::Displacement(_)));
+        let text = match program.target(index) {
+            Some(target) if relative => {
+                let operands = instruction
+                    .operands()
+                    .map(|operand| match operand {
+                        Operand::Displacement(_) => label(program, symbols, target),
+                        operand => format!("{}", operand),
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("{} {}", instruction.opcode(), operands.join(", "))
+            }
+            _ => instruction.into_assembly(),
+        };
+
+        output.push_str(&format!("    {}\n", text));
+    }
+
+    return output;
+}
+
+/// The name of the instruction at an index, the symbol starting at it or a generated label.
+fn label(program: &Program, symbols: &SymbolTable, index: usize) -> String {
+    let offset = program.offset_of(index).unwrap_or(0);
+
+    return match symbols.symbol_at(offset) {
+        Some(symbol) if symbol.offset() == offset => symbol.name().to_string(),
+        _ => format!("L{}", offset),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembler::{assemble, assemble_file};
+
+    #[test]
+    pub fn test_round_trip() {
+        let source = "start: ldi $r0, 0i-3\ncmpi $r0, $r1\njne start\njmp16 end\njmp8 0d0\nend: jmp 0u0\nhalt\n";
+        let bytes = assemble(source).unwrap();
+        let text = disassemble(&bytes).unwrap();
+
+        assert_eq!(
+            text,
+            "L0:\n    ldi $r0, 0i-3\n    cmpi $r0, $r1\n    jne8 L0\n    jmp16 L22\n    jmp8 L22\nL22:\n    jmp 0u0\n    halt\n"
+        );
+        assert_eq!(assemble(&text), Ok(bytes));
+    }
+
+    #[test]
+    pub fn test_symbol_names() {
+        let source = "main: call square\nhalt\nsquare: muli $r0, $r0, $r0\nloop: jne loop\nret\n";
+        let file = assemble_file(source, InstructionEncoding::Compact).unwrap();
+        let text = disassemble_file(&file).unwrap();
+
+        assert_eq!(
+            text,
+            "main:\n    call8 square\n    halt\nsquare:\n    muli $r0, $r0, $r0\nloop:\n    jne8 loop\n    ret\n"
+        );
+        assert_eq!(assemble_file(&text, InstructionEncoding::Compact), Ok(file));
+    }
+
+    #[test]
+    pub fn test_locations() {
+        let source = ".loc \"main.vl\" 1 1\nldi $r0, 0i1\n.loc \"main.vl\" 2 3\nloop: jne loop\n";
+        let file = assemble_file(source, InstructionEncoding::Fixed).unwrap();
+        let text = disassemble_file(&file).unwrap();
+
+        assert_eq!(
+            text,
+            "    .loc \"main.vl\" 1 1\n    ldi $r0, 0i1\nloop:\n    .loc \"main.vl\" 2 3\n    jne8 loop\n"
+        );
+        assert_eq!(assemble_file(&text, InstructionEncoding::Fixed), Ok(file));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::byte_sink::{ByteSink, EncodeError, SliceSink};
+use crate::instruction::{Instruction, Opcode};
+use crate::instruction_arguments::Displacement;
+
+/// The largest number of bytes a value takes up in the compact encoding.
+pub const MAX_COMPACT_VALUE_LEN: usize = 10;
+
+/// The encodings a program can be stored in, a vxl file records the encoding of its program in its
+/// version.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub enum InstructionEncoding {
+    /// Every immediate and address takes up 8 bytes, so the size of an instruction only depends on
+    /// its opcode. Used by version 0.
+    #[default]
+    Fixed,
+    /// Integer immediates and addresses are written as LEB128, signed immediates being zigzag
+    /// encoded first, so small values take up a single byte. Float immediates and every other
+    /// operand are written as in the fixed encoding. Used by version 1.
+    Compact,
+}
+
+/// How a value is written in the compact encoding.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum CompactForm {
+    /// Unsigned LEB128.
+    Unsigned,
+    /// The value is treated as a signed integer and zigzag encoded before being written as unsigned
+    /// LEB128, so values close to zero are short whatever their sign.
+    Signed,
+    /// The 8 bytes of the value, little endian.
+    Fixed,
+}
+
+impl InstructionEncoding {
+    /// The version of the vxl files holding programs in this encoding.
+    pub const fn version(self) -> u8 {
+        return match self {
+            InstructionEncoding::Fixed => 0,
+            InstructionEncoding::Compact => 1,
+        };
+    }
+
+    /// Returns the encoding of programs in files of a version, or None if the version is unknown.
+    pub const fn for_version(version: u8) -> Option<Self> {
+        return match version {
+            0 => Some(InstructionEncoding::Fixed),
+            1 => Some(InstructionEncoding::Compact),
+            _ => None,
+        };
+    }
+
+    /// The number of bytes an instruction takes up in this encoding.
+    pub fn encoded_len(self, instruction: &Instruction) -> usize {
+        return match self {
+            InstructionEncoding::Fixed => instruction.encoded_len(),
+            InstructionEncoding::Compact => {
+                let mut buffer = [0; Opcode::MAX_COMPACT_LEN];
+
+                // Displacements take up their declared width whatever their value, zeroing them
+                // keeps the length of instructions that cannot be encoded.
+                instruction
+                    .map_displacements(|_| Displacement::new(0))
+                    .encode_compact_to(&mut SliceSink::new(&mut buffer))
+                    .expect("MAX_COMPACT_LEN holds every instruction")
+            }
+        };
+    }
+
+    /// Encodes an instruction into a sink, returning the number of bytes written.
+    pub fn encode_to<S: ByteSink>(
+        self,
+        instruction: &Instruction,
+        sink: &mut S,
+    ) -> Result<usize, EncodeError<S::Error>> {
+        return match self {
+            InstructionEncoding::Fixed => instruction.encode_to(sink),
+            InstructionEncoding::Compact => instruction.encode_compact_to(sink),
+        };
+    }
+
+    /// Decodes the instruction at the start of a byte slice, returning it along with the number of
+    /// bytes it took up.
+    pub fn decode(self, bytes: &[u8]) -> Option<(Instruction, usize)> {
+        return match self {
+            InstructionEncoding::Fixed => Instruction::from_bytes(bytes),
+            InstructionEncoding::Compact => Instruction::from_compact_bytes(bytes),
+        };
+    }
+}
+
+/// Writes a value at the start of a buffer of at least `MAX_COMPACT_VALUE_LEN` bytes, returning the
+/// number of bytes written.
+pub fn write_compact(value: u64, form: CompactForm, buffer: &mut [u8]) -> usize {
+    let mut value = match form {
+        CompactForm::Unsigned => value,
+        CompactForm::Signed => ((value << 1) as i64 ^ (value as i64 >> 63)) as u64,
+        CompactForm::Fixed => {
+            buffer[..8].copy_from_slice(&value.to_le_bytes());
+
+            return 8;
+        }
+    };
+    let mut len = 0;
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            buffer[len] = byte;
+
+            return len + 1;
+        }
+
+        buffer[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+/// Reads a value from the start of a byte slice, returning it along with the number of bytes it took
+/// up. Returns None if the bytes end early, or if the value does not fit in 64 bits or is not
+/// written in as few bytes as possible, so every value has a single encoding.
+pub fn read_compact(bytes: &[u8], form: CompactForm) -> Option<(u64, usize)> {
+    if form == CompactForm::Fixed {
+        return Some((u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?), 8));
+    }
+
+    let mut value = 0;
+
+    for (index, byte) in bytes.iter().take(MAX_COMPACT_VALUE_LEN).enumerate() {
+        let bits = (byte & 0x7f) as u64;
+
+        if (index == MAX_COMPACT_VALUE_LEN - 1 && bits > 1) || (index > 0 && *byte == 0) {
+            return None;
+        }
+
+        value |= bits << (7 * index);
+
+        if byte & 0x80 == 0 {
+            let value = match form {
+                CompactForm::Signed => (value >> 1) ^ (value & 1).wrapping_neg(),
+                _ => value,
+            };
+
+            return Some((value, index + 1));
+        }
+    }
+
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction_arguments::{Address, Condition, Displacement, Immediate, Register};
+    use alloc::vec::Vec;
+
+    #[test]
+    pub fn test_compact_values() {
+        let mut buffer = [0; MAX_COMPACT_VALUE_LEN];
+
+        for (value, form, len) in [
+            (0, CompactForm::Unsigned, 1),
+            (127, CompactForm::Unsigned, 1),
+            (128, CompactForm::Unsigned, 2),
+            (u64::MAX, CompactForm::Unsigned, 10),
+            (-1i64 as u64, CompactForm::Signed, 1),
+            (-64i64 as u64, CompactForm::Signed, 1),
+            (64, CompactForm::Signed, 2),
+            (i64::MIN as u64, CompactForm::Signed, 10),
+            (1.5f64.to_bits(), CompactForm::Fixed, 8),
+        ] {
+            assert_eq!(write_compact(value, form, &mut buffer), len);
+            assert_eq!(read_compact(&buffer[..len], form), Some((value, len)));
+        }
+
+        assert_eq!(read_compact(&[0x80], CompactForm::Unsigned), None);
+        assert_eq!(read_compact(&[0x80, 0x00], CompactForm::Unsigned), None);
+        assert_eq!(read_compact(&[0xff; 10], CompactForm::Unsigned), None);
+    }
+
+    #[test]
+    pub fn test_compact_instructions() {
+        let instructions = [
+            Instruction::Ldb(Immediate::from(1u64), Register::R0),
+            Instruction::Ldi(Immediate::from(-300i64), Register::R3),
+            Instruction::Ldf(Immediate::from(0.25), Register::R1),
+            Instruction::Csel(Condition::GEU, Register::R0, Register::R1, Register::R2),
+            Instruction::Swpa(Address::heap(3), Address::heap(70000)),
+            Instruction::Jne16(Displacement::new(-400)),
+            Instruction::Call(Address::code(12)),
+        ];
+        let lens = [3, 4, 10, 3, 5, 4, 2];
+
+        for (instruction, len) in instructions.iter().zip(lens) {
+            let mut bytes = Vec::new();
+
+            assert_eq!(
+                InstructionEncoding::Compact.encode_to(instruction, &mut bytes),
+                Ok(len)
+            );
+            assert_eq!(InstructionEncoding::Compact.encoded_len(instruction), len);
+            assert!(len <= Opcode::MAX_COMPACT_LEN);
+            assert_eq!(
+                InstructionEncoding::Compact.decode(&bytes),
+                Some((*instruction, len))
+            );
+            assert_eq!(InstructionEncoding::Compact.decode(&bytes[..len - 1]), None);
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use crate::exception::Fault;
+use crate::instruction::Opcode;
+use crate::instruction_arguments::{Condition, Immediate, ImmediateType};
+
+// Register values are raw 64-bit values, each function interprets them the way its instruction
+// does. Integer arithmetic wraps, shift and rotate counts are taken modulo 64 and float arithmetic
+// follows IEEE 754 with the default rounding mode.
+
+/// `addi`, wrapping signed addition.
+pub const fn addi(a: u64, b: u64) -> u64 {
+    return (a as i64).wrapping_add(b as i64) as u64;
+}
+
+/// `subi`, wrapping signed subtraction.
+pub const fn subi(a: u64, b: u64) -> u64 {
+    return (a as i64).wrapping_sub(b as i64) as u64;
+}
+
+/// `muli`, wrapping signed multiplication.
+pub const fn muli(a: u64, b: u64) -> u64 {
+    return (a as i64).wrapping_mul(b as i64) as u64;
+}
+
+/// `divi`, signed division rounding towards zero. Dividing the smallest value by -1 wraps.
+pub const fn divi(a: u64, b: u64) -> Result<u64, Fault> {
+    if b == 0 {
+        return Err(Fault::DivisionByZero);
+    }
+
+    return Ok((a as i64).wrapping_div(b as i64) as u64);
+}
+
+/// `modi`, the remainder of `divi` which takes the sign of the dividend.
+pub const fn modi(a: u64, b: u64) -> Result<u64, Fault> {
+    if b == 0 {
+        return Err(Fault::DivisionByZero);
+    }
+
+    return Ok((a as i64).wrapping_rem(b as i64) as u64);
+}
+
+/// `addu`, wrapping unsigned addition.
+pub const fn addu(a: u64, b: u64) -> u64 {
+    return a.wrapping_add(b);
+}
+
+/// `subu`, wrapping unsigned subtraction.
+pub const fn subu(a: u64, b: u64) -> u64 {
+    return a.wrapping_sub(b);
+}
+
+/// `mulu`, wrapping unsigned multiplication.
+pub const fn mulu(a: u64, b: u64) -> u64 {
+    return a.wrapping_mul(b);
+}
+
+/// `divu`, unsigned division.
+pub const fn divu(a: u64, b: u64) -> Result<u64, Fault> {
+    if b == 0 {
+        return Err(Fault::DivisionByZero);
+    }
+
+    return Ok(a / b);
+}
+
+/// `modu`, unsigned remainder.
+pub const fn modu(a: u64, b: u64) -> Result<u64, Fault> {
+    if b == 0 {
+        return Err(Fault::DivisionByZero);
+    }
+
+    return Ok(a % b);
+}
+
+/// `addf`
+pub fn addf(a: u64, b: u64) -> u64 {
+    return (f64::from_bits(a) + f64::from_bits(b)).to_bits();
+}
+
+/// `subf`
+pub fn subf(a: u64, b: u64) -> u64 {
+    return (f64::from_bits(a) - f64::from_bits(b)).to_bits();
+}
+
+/// `mulf`
+pub fn mulf(a: u64, b: u64) -> u64 {
+    return (f64::from_bits(a) * f64::from_bits(b)).to_bits();
+}
+
+/// `divf`, dividing by zero gives an infinity or NaN rather than a fault.
+pub fn divf(a: u64, b: u64) -> u64 {
+    return (f64::from_bits(a) / f64::from_bits(b)).to_bits();
+}
+
+/// `rotl` and `rotli`
+pub const fn rotl(value: u64, count: u64) -> u64 {
+    return value.rotate_left((count % 64) as u32);
+}
+
+/// `rotr` and `rotri`
+pub const fn rotr(value: u64, count: u64) -> u64 {
+    return value.rotate_right((count % 64) as u32);
+}
+
+/// `sll` and `slli`
+pub const fn sll(value: u64, count: u64) -> u64 {
+    return value << (count % 64);
+}
+
+/// `srl` and `srli`
+pub const fn srl(value: u64, count: u64) -> u64 {
+    return value >> (count % 64);
+}
+
+/// `not`
+pub const fn not(value: u64) -> u64 {
+    return !value;
+}
+
+/// `and`
+pub const fn and(a: u64, b: u64) -> u64 {
+    return a & b;
+}
+
+/// `or`
+pub const fn or(a: u64, b: u64) -> u64 {
+    return a | b;
+}
+
+/// `xor`
+pub const fn xor(a: u64, b: u64) -> u64 {
+    return a ^ b;
+}
+
+/// `cmp`, the flags register after comparing unsigned integers.
+pub const fn cmp(a: u64, b: u64) -> u64 {
+    return compare_flags(a == b, a < b, a > b) | unsigned_flags(a, b);
+}
+
+/// `cmpi`, the flags register after comparing signed integers.
+pub const fn cmpi(a: u64, b: u64) -> u64 {
+    let (signed_a, signed_b) = (a as i64, b as i64);
+
+    return compare_flags(a == b, signed_a < signed_b, signed_a > signed_b) | unsigned_flags(a, b);
+}
+
+/// `cmpf`, the flags register after comparing floats. No flags are set if either is NaN.
+pub fn cmpf(a: u64, b: u64) -> u64 {
+    let (a, b) = (f64::from_bits(a), f64::from_bits(b));
+
+    return compare_flags(a == b, a < b, a > b);
+}
+
+/// `setcc`
+pub const fn setcc(condition: Condition, flags: u64) -> u64 {
+    return condition.evaluate(flags) as u64;
+}
+
+/// `csel`
+pub const fn csel(condition: Condition, flags: u64, a: u64, b: u64) -> u64 {
+    if condition.evaluate(flags) {
+        return a;
+    } else {
+        return b;
+    }
+}
+
+/// `i2f`, converts a signed integer to the nearest float.
+pub fn i2f(value: u64) -> u64 {
+    return (value as i64 as f64).to_bits();
+}
+
+/// `f2i`, converts a float to a signed integer rounding towards zero. Values out of range saturate
+/// and NaN converts to 0.
+pub fn f2i(value: u64) -> u64 {
+    return f64::from_bits(value) as i64 as u64;
+}
+
+/// `absf`
+#[cfg(feature = "float-ext")]
+pub const fn absf(value: u64) -> u64 {
+    return value & !(1 << 63);
+}
+
+/// `negf`
+#[cfg(feature = "float-ext")]
+pub const fn negf(value: u64) -> u64 {
+    return value ^ (1 << 63);
+}
+
+/// `minf`, if one of the floats is NaN the other is returned.
+#[cfg(feature = "float-ext")]
+pub fn minf(a: u64, b: u64) -> u64 {
+    return f64::from_bits(a).min(f64::from_bits(b)).to_bits();
+}
+
+/// `maxf`, if one of the floats is NaN the other is returned.
+#[cfg(feature = "float-ext")]
+pub fn maxf(a: u64, b: u64) -> u64 {
+    return f64::from_bits(a).max(f64::from_bits(b)).to_bits();
+}
+
+/// `clz`
+#[cfg(feature = "bitmanip")]
+pub const fn clz(value: u64) -> u64 {
+    return value.leading_zeros() as u64;
+}
+
+/// `ctz`
+#[cfg(feature = "bitmanip")]
+pub const fn ctz(value: u64) -> u64 {
+    return value.trailing_zeros() as u64;
+}
+
+/// `popcnt`
+#[cfg(feature = "bitmanip")]
+pub const fn popcnt(value: u64) -> u64 {
+    return value.count_ones() as u64;
+}
+
+/// `bswap`
+#[cfg(feature = "bitmanip")]
+pub const fn bswap(value: u64) -> u64 {
+    return value.swap_bytes();
+}
+
+/// `andn`
+#[cfg(feature = "bitmanip")]
+pub const fn andn(a: u64, b: u64) -> u64 {
+    return a & !b;
+}
+
+/// `sra` and `srai`
+#[cfg(feature = "bitmanip")]
+pub const fn sra(value: u64, count: u64) -> u64 {
+    return ((value as i64) >> (count % 64)) as u64;
+}
+
+/// Evaluates an instruction on known source values, for constant folding. The values are given in
+/// the order the instruction reads them, e.g. `[r2, r3]` for `addi r1, r2, r3` and `[r1, i]` for
+/// `slli r1, i`. The result is typed according to the instruction, floats for float arithmetic,
+/// signed integers for signed arithmetic and unsigned integers otherwise.
+///
+/// Returns None if the instruction does not compute a value from its sources or the wrong number of
+/// values is given.
+pub fn fold(opcode: Opcode, values: &[Immediate]) -> Option<Result<Immediate, Fault>> {
+    use ImmediateType::{Float, SignedInteger as Signed, UnsignedInteger as Unsigned};
+
+    let (value, tp) = match (opcode, values) {
+        (Opcode::Addi, [a, b]) => (Ok(addi(a.as_u64(), b.as_u64())), Signed),
+        (Opcode::Subi, [a, b]) => (Ok(subi(a.as_u64(), b.as_u64())), Signed),
+        (Opcode::Muli, [a, b]) => (Ok(muli(a.as_u64(), b.as_u64())), Signed),
+        (Opcode::Divi, [a, b]) => (divi(a.as_u64(), b.as_u64()), Signed),
+        (Opcode::Modi, [a, b]) => (modi(a.as_u64(), b.as_u64()), Signed),
+        (Opcode::Addu, [a, b]) => (Ok(addu(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Subu, [a, b]) => (Ok(subu(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Mulu, [a, b]) => (Ok(mulu(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Divu, [a, b]) => (divu(a.as_u64(), b.as_u64()), Unsigned),
+        (Opcode::Modu, [a, b]) => (modu(a.as_u64(), b.as_u64()), Unsigned),
+        (Opcode::Addf, [a, b]) => (Ok(addf(a.as_u64(), b.as_u64())), Float),
+        (Opcode::Subf, [a, b]) => (Ok(subf(a.as_u64(), b.as_u64())), Float),
+        (Opcode::Mulf, [a, b]) => (Ok(mulf(a.as_u64(), b.as_u64())), Float),
+        (Opcode::Divf, [a, b]) => (Ok(divf(a.as_u64(), b.as_u64())), Float),
+        (Opcode::Rotl | Opcode::Rotli, [a, b]) => (Ok(rotl(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Rotr | Opcode::Rotri, [a, b]) => (Ok(rotr(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Sll | Opcode::Slli, [a, b]) => (Ok(sll(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Srl | Opcode::Srli, [a, b]) => (Ok(srl(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Not, [a]) => (Ok(not(a.as_u64())), Unsigned),
+        (Opcode::And, [a, b]) => (Ok(and(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Or, [a, b]) => (Ok(or(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Xor, [a, b]) => (Ok(xor(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Cmp, [a, b]) => (Ok(cmp(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Cmpi, [a, b]) => (Ok(cmpi(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::Cmpf, [a, b]) => (Ok(cmpf(a.as_u64(), b.as_u64())), Unsigned),
+        (Opcode::I2f, [a]) => (Ok(i2f(a.as_u64())), Float),
+        (Opcode::F2i, [a]) => (Ok(f2i(a.as_u64())), Signed),
+        #[cfg(feature = "float-ext")]
+        (Opcode::Absf, [a]) => (Ok(absf(a.as_u64())), Float),
+        #[cfg(feature = "float-ext")]
+        (Opcode::Negf, [a]) => (Ok(negf(a.as_u64())), Float),
+        #[cfg(feature = "float-ext")]
+        (Opcode::Minf, [a, b]) => (Ok(minf(a.as_u64(), b.as_u64())), Float),
+        #[cfg(feature = "float-ext")]
+        (Opcode::Maxf, [a, b]) => (Ok(maxf(a.as_u64(), b.as_u64())), Float),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Clz, [a]) => (Ok(clz(a.as_u64())), Unsigned),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Ctz, [a]) => (Ok(ctz(a.as_u64())), Unsigned),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Popcnt, [a]) => (Ok(popcnt(a.as_u64())), Unsigned),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Bswap, [a]) => (Ok(bswap(a.as_u64())), Unsigned),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Andn, [a, b]) => (Ok(andn(a.as_u64(), b.as_u64())), Unsigned),
+        #[cfg(feature = "bitmanip")]
+        (Opcode::Sra | Opcode::Srai, [a, b]) => (Ok(sra(a.as_u64(), b.as_u64())), Signed),
+        _ => return None,
+    };
+
+    return Some(value.map(|value| Immediate::from(value).with_type(tp)));
+}
+
+const fn compare_flags(equal: bool, less: bool, greater: bool) -> u64 {
+    let mut flags = 0;
+
+    if equal {
+        flags |= Condition::EQUAL_FLAG;
+    }
+
+    if less {
+        flags |= Condition::LESS_FLAG;
+    }
+
+    if greater {
+        flags |= Condition::GREATER_FLAG;
+    }
+
+    return flags;
+}
+
+const fn unsigned_flags(a: u64, b: u64) -> u64 {
+    if a < b {
+        return Condition::UNSIGNED_LESS_FLAG;
+    } else if a > b {
+        return Condition::UNSIGNED_GREATER_FLAG;
+    } else {
+        return 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_integer_arithmetic() {
+        assert_eq!(addu(u64::MAX, 2), 1);
+        assert_eq!(subi(0, 1) as i64, -1);
+        assert_eq!(divi(-7i64 as u64, 2), Ok(-3i64 as u64));
+        assert_eq!(modi(-7i64 as u64, 2), Ok(-1i64 as u64));
+        assert_eq!(divi(i64::MIN as u64, -1i64 as u64), Ok(i64::MIN as u64));
+        assert_eq!(divu(7, 0), Err(Fault::DivisionByZero));
+        assert_eq!(modi(7, 0), Err(Fault::DivisionByZero));
+        assert_eq!(sll(1, 65), 2);
+        assert_eq!(rotr(1, 1), 1 << 63);
+        assert_eq!(f2i(f64::NAN.to_bits()), 0);
+        assert_eq!(f2i((-2.9f64).to_bits()) as i64, -2);
+    }
+
+    #[test]
+    pub fn test_compare() {
+        let flags = cmpi(-1i64 as u64, 1);
+
+        assert!(Condition::LT.evaluate(flags));
+        assert!(Condition::GTU.evaluate(flags));
+        assert!(Condition::GT.evaluate(cmp(-1i64 as u64, 1)));
+        assert_eq!(cmpf(f64::NAN.to_bits(), 1f64.to_bits()), 0);
+        assert_eq!(setcc(Condition::EQ, cmp(3, 3)), 1);
+    }
+
+    #[test]
+    pub fn test_fold() {
+        assert_eq!(
+            fold(Opcode::Addf, &[Immediate::from(1.5), Immediate::from(2.25)]),
+            Some(Ok(Immediate::from(3.75)))
+        );
+        assert_eq!(
+            fold(
+                Opcode::Muli,
+                &[Immediate::from(-3i64), Immediate::from(4i64)]
+            ),
+            Some(Ok(Immediate::from(-12i64)))
+        );
+        assert_eq!(
+            fold(Opcode::I2f, &[Immediate::from(-2i64)]),
+            Some(Ok(Immediate::from(-2.0)))
+        );
+        assert_eq!(
+            fold(
+                Opcode::Divu,
+                &[Immediate::from(1u64), Immediate::from(0u64)]
+            ),
+            Some(Err(Fault::DivisionByZero))
+        );
+        assert_eq!(fold(Opcode::Addi, &[Immediate::from(1u64)]), None);
+        assert_eq!(fold(Opcode::Jmp, &[]), None);
+    }
+}
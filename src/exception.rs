@@ -0,0 +1,164 @@
+use crate::instruction_arguments::Address;
+use alloc::vec::Vec;
+
+/// The exception codes used for faults raised by the vm itself. Codes below
+/// `Fault::FIRST_PROGRAM_CODE` are reserved for faults, programs should throw codes from it onwards.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[repr(u64)]
+pub enum Fault {
+    /// An integer division or modulo by zero.
+    DivisionByZero = 1,
+    /// Memory was freed that is not currently allocated.
+    InvalidFree = 2,
+    /// Allocated memory was accessed outside of its bounds.
+    OutOfBounds = 3,
+    /// A value was popped or accessed past the bottom of the stack.
+    StackUnderflow = 4,
+    /// The stack grew past the limit of the vm.
+    StackOverflow = 5,
+    /// An allocation could not be satisfied.
+    OutOfMemory = 6,
+    /// The bytes at the current offset are not a valid instruction.
+    InvalidInstruction = 7,
+    /// `endtry` was executed without a registered handler.
+    NoHandler = 8,
+}
+
+/// A registered exception handler, the state recorded by `try`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Handler {
+    address: Address,
+    rsp: u64,
+    rfp: u64,
+}
+
+/// The stack of registered exception handlers. This is kept separately to the value stack so that
+/// registering a handler does not change the rfp relative offsets of a frame.
+///
+/// # Execution model
+/// `try a` pushes a handler recording `a` along with the current values of rsp and rfp, and
+/// `endtry` pops the most recently registered handler. Executing `endtry` with no handlers raises
+/// `Fault::NoHandler`.
+///
+/// `throw r` and `throwi i` pop the most recently registered handler, restore rsp and rfp to the
+/// recorded values, store the exception code in rou and continue execution at the handler
+/// address. Restoring rsp discards every value, frame and return offset pushed since the handler
+/// was registered, which unwinds the call stack. Faults raised by the vm are thrown in the same way
+/// using their `Fault` code. An exception thrown with no registered handlers is unhandled and stops
+/// execution, it is reported to the host through `ExecuteInstruction::Output`.
+///
+/// Handlers do not outlive the frame they were registered in, after `leave` and `ret` execute
+/// every handler that recorded a larger rsp than the new rsp is discarded.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct HandlerStack {
+    handlers: Vec<Handler>,
+}
+
+impl Fault {
+    /// The first exception code available to programs.
+    pub const FIRST_PROGRAM_CODE: u64 = 256;
+
+    /// The exception code for this fault.
+    pub const fn code(self) -> u64 {
+        return self as u64;
+    }
+}
+
+impl Handler {
+    /// Creates a new handler.
+    pub fn new(address: Address, rsp: u64, rfp: u64) -> Self {
+        return Self { address, rsp, rfp };
+    }
+
+    /// The address execution continues from when this handler catches an exception.
+    pub fn address(&self) -> Address {
+        return self.address;
+    }
+
+    /// The value rsp is restored to when this handler catches an exception.
+    pub fn rsp(&self) -> u64 {
+        return self.rsp;
+    }
+
+    /// The value rfp is restored to when this handler catches an exception.
+    pub fn rfp(&self) -> u64 {
+        return self.rfp;
+    }
+}
+
+impl HandlerStack {
+    /// Creates an empty handler stack.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Registers a handler, as done by `try`.
+    pub fn push(&mut self, handler: Handler) {
+        self.handlers.push(handler);
+    }
+
+    /// Removes the most recently registered handler, as done by `endtry` and when an exception is
+    /// thrown.
+    pub fn pop(&mut self) -> Option<Handler> {
+        return self.handlers.pop();
+    }
+
+    /// The handler that would catch an exception thrown now.
+    pub fn innermost(&self) -> Option<&Handler> {
+        return self.handlers.last();
+    }
+
+    /// Discards every handler that recorded a larger rsp than the given one, as done after `leave`
+    /// and `ret`.
+    pub fn discard_above(&mut self, rsp: u64) {
+        while let Some(handler) = self.handlers.last() {
+            if handler.rsp <= rsp {
+                break;
+            }
+
+            self.handlers.pop();
+        }
+    }
+
+    /// The number of registered handlers.
+    pub fn len(&self) -> usize {
+        return self.handlers.len();
+    }
+
+    /// True if there are no registered handlers.
+    pub fn is_empty(&self) -> bool {
+        return self.handlers.is_empty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_innermost_handler_catches() {
+        let mut handlers = HandlerStack::new();
+
+        handlers.push(Handler::new(Address::new(10), 2, 0));
+        handlers.push(Handler::new(Address::new(40), 6, 4));
+
+        assert_eq!(handlers.pop(), Some(Handler::new(Address::new(40), 6, 4)));
+        assert_eq!(handlers.pop(), Some(Handler::new(Address::new(10), 2, 0)));
+        assert_eq!(handlers.pop(), None);
+    }
+
+    #[test]
+    pub fn test_discard_above() {
+        let mut handlers = HandlerStack::new();
+
+        handlers.push(Handler::new(Address::new(10), 2, 0));
+        handlers.push(Handler::new(Address::new(40), 6, 4));
+        handlers.push(Handler::new(Address::new(80), 9, 7));
+
+        // Returning to a frame with 6 values on the stack keeps the handler registered there.
+        handlers.discard_above(6);
+
+        assert_eq!(handlers.len(), 2);
+        assert_eq!(handlers.innermost().unwrap().address(), Address::new(40));
+    }
+}
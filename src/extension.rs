@@ -0,0 +1,149 @@
+use crate::instruction::{Instruction, Opcode};
+use crate::instruction_info::InstructionInfo;
+use core::ops::RangeInclusive;
+
+include!("../build/extensions.rs");
+
+/// An optional set of instructions, each is declared in its own table in `extensions` and only
+/// included in `Instruction` when the cargo feature of the same name is enabled.
+///
+/// # Opcodes
+/// The base instruction set uses opcodes `0x00` to `0x7f` along with the two byte opcodes from
+/// `0xff00`. Each extension has a range of 32 opcodes of its own, `0xe0` to `0xfe` are unassigned
+/// and `0xff` is reserved as an escape prefix.
+///
+/// # Header flags
+/// Bits 1 to 3 of the flags of a `VXLHeader` record the extensions a program requires, a vm should
+/// refuse to run a program requiring an extension it does not support.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[repr(u8)]
+pub enum Extension {
+    /// Floating point instructions beyond basic arithmetic, enabled by `float-ext`.
+    FloatExt,
+    /// Bit manipulation instructions, enabled by `bitmanip`.
+    Bitmanip,
+    /// Instructions querying or synchronising with the host, enabled by `system`.
+    System,
+}
+
+impl Extension {
+    /// Every extension, whether or not it is enabled.
+    pub const ALL: [Extension; 3] = [Extension::FloatExt, Extension::Bitmanip, Extension::System];
+
+    /// The name of the cargo feature enabling the extension.
+    pub const fn name(self) -> &'static str {
+        return match self {
+            Extension::FloatExt => "float-ext",
+            Extension::Bitmanip => "bitmanip",
+            Extension::System => "system",
+        };
+    }
+
+    /// The opcodes reserved for the extension, read from the table the build script generates the
+    /// extensions from.
+    pub const fn opcode_range(self) -> RangeInclusive<u8> {
+        let (_, _, _, first, last) = EXTENSIONS[self as usize];
+
+        return first as u8..=last as u8;
+    }
+
+    /// The bit of the header flags set when a program requires the extension.
+    pub const fn header_flag(self) -> u8 {
+        return match self {
+            Extension::FloatExt => 0b0000_0010,
+            Extension::Bitmanip => 0b0000_0100,
+            Extension::System => 0b0000_1000,
+        };
+    }
+
+    /// True if the instructions of the extension were included in this build.
+    pub const fn is_enabled(self) -> bool {
+        return match self {
+            Extension::FloatExt => cfg!(feature = "float-ext"),
+            Extension::Bitmanip => cfg!(feature = "bitmanip"),
+            Extension::System => cfg!(feature = "system"),
+        };
+    }
+
+    /// Returns the extension whose range holds an opcode, or None for opcodes outside of every
+    /// extension.
+    pub fn for_opcode(opcode: Opcode) -> Option<Extension> {
+        let opcode = u8::try_from(opcode.value()).ok()?;
+
+        return Self::ALL
+            .into_iter()
+            .find(|extension| extension.opcode_range().contains(&opcode));
+    }
+
+    /// Returns the header flags of the extensions used by a sequence of instructions.
+    pub fn required_flags<'a, I: IntoIterator<Item = &'a Instruction>>(instructions: I) -> u8 {
+        return instructions
+            .into_iter()
+            .filter_map(|instruction| instruction.info().extension())
+            .fold(0, |flags, extension| flags | extension.header_flag());
+    }
+}
+
+impl InstructionInfo {
+    /// True if the instruction can be used by programs that require no extensions.
+    pub fn is_base(&self) -> bool {
+        return self.extension().is_none();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::InstructionEncoding;
+    use crate::instruction_arguments::Register;
+    use crate::vxl_file::{VXLBuilder, VXLHeader};
+
+    #[test]
+    pub fn test_opcode_ranges() {
+        for opcode in Opcode::iter() {
+            assert_eq!(Extension::for_opcode(opcode), opcode.info().extension());
+            assert!(opcode.info().extension().is_none_or(Extension::is_enabled));
+        }
+
+        for extension in Extension::ALL {
+            let (_, feature, variant, _, _) = EXTENSIONS[extension as usize];
+
+            assert_eq!(variant, alloc::format!("{:?}", extension));
+            assert_eq!(feature, extension.name().to_uppercase().replace('-', "_"));
+        }
+
+        assert_eq!(
+            Extension::required_flags(&[Instruction::Nop, Instruction::Push(Register::R0)]),
+            0
+        );
+
+        let file = VXLBuilder::new()
+            .flags(1)
+            .code("code", Instruction::Nop.into(), InstructionEncoding::Fixed)
+            .build();
+
+        assert_eq!(file.header().flags() & VXLHeader::EXTENSIONS_MASK, 0);
+        assert_eq!(file.header().flags(), 1);
+    }
+
+    #[cfg(feature = "bitmanip")]
+    #[test]
+    pub fn test_bitmanip_instruction() {
+        let instruction = Instruction::Popcnt(Register::R0, Register::R1);
+        let bytes: alloc::vec::Vec<u8> = instruction.into();
+
+        assert_eq!(bytes, alloc::vec![0xa2, 0b0110_0111]);
+        assert_eq!(Instruction::from_bytes(&bytes), Some((instruction, 2)));
+        assert_eq!(
+            Extension::required_flags(&[instruction]),
+            Extension::Bitmanip.header_flag()
+        );
+
+        let source = alloc::format!("{}\nhalt", instruction.into_assembly());
+        let file = crate::assembler::assemble_file(&source, InstructionEncoding::Compact).unwrap();
+
+        assert!(file.header().requires_extension(Extension::Bitmanip));
+        assert!(!file.header().requires_extension(Extension::System));
+        assert_eq!(file.header().missing_extensions().count(), 0);
+    }
+}
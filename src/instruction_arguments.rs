@@ -1,17 +1,38 @@
 use alloc::vec;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt;
 
 /// Defines some properties any instruction argument should have.
-pub trait InstructionArgument {
+pub trait InstructionArgument: Sized {
     /// The number of bits for this instruction argument type.
     const BIT_SIZE: usize;
 
     /// The number of bytes for this instruction argument type.
     const BYTES: usize = (Self::BIT_SIZE + Self::BIT_SIZE % 8) / 8;
 
+    /// The kind of operand this argument type represents.
+    const KIND: OperandKind;
+
     /// Should guarantee a conversion to a vector of bytes.
     fn into_bytes(self) -> Vec<u8>;
+
+    /// Should attempt the conversion from exactly `BYTES` bytes, or from the declared width of
+    /// arguments encoded in one. Arguments smaller than a byte are held in the low bits. Returns None
+    /// if the bytes do not represent a valid argument.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Identifies the kind of an instruction argument. The value of each variant is the type code used by
+/// `Opcode::get_type_for_index`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[repr(u8)]
+pub enum OperandKind {
+    Register = 0,
+    Immediate = 1,
+    Address = 2,
+    Condition = 3,
+    Displacement = 4,
 }
 
 /// This enum defines all the possible registers supported by the voxl-vm and their value as a byte.
@@ -24,7 +45,8 @@ pub trait InstructionArgument {
 ///
 /// 0010 - rou - Result from instructions/returns
 ///
-/// 0011 - rfl - Flags register (Largely just reserved. LSB 1 - equal, LSB 2 - less than, LSB 3 - greater than)
+/// 0011 - rfl - Flags register (LSB 1 - equal, LSB 2 - less than, LSB 3 - greater than,
+/// LSB 4 - unsigned less than, LSB 5 - unsigned greater than)
 ///
 /// ### Reserved registers
 /// 0100 - rra - Reserved a
@@ -91,16 +113,42 @@ pub enum Register {
     R9 = 15,
 }
 
-/// This struct represents an address in memory, it is an absolute value and used in multiple contexts,
-/// it can be used to represent the unique identifier of heap allocated memory or the offset from the
-/// start of the program for a specific instruction
+/// This struct represents an address, it is an absolute value tagged with what it refers to. Code
+/// addresses are the offset from the start of the program for a specific instruction and are the
+/// only addresses relocated when linking. Heap addresses are the unique identifier of heap allocated
+/// memory.
+///
+/// The kind is not encoded, each address operand of an instruction is declared as one or the other
+/// and decoding restores it.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Address {
     absolute_address: u64,
+    kind: AddressKind,
 }
 
-/// Represents the type of an Immediate
+/// What an `Address` refers to.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum AddressKind {
+    /// The offset of an instruction from the start of the program.
+    Code,
+    /// The identifier of heap allocated memory.
+    Heap,
+}
+
+/// A signed offset from the end of a relative branch to its target, so the branch reaches the same
+/// instruction wherever the code holding both is placed.
+///
+/// Each relative branch declares the width its displacement is encoded in, 8, 16 or 32 bits.
+/// Encoding a branch whose displacement does not fit in that width fails with
+/// `EncodeError::DisplacementOutOfRange`, use `Displacement::with_width` to construct one checked
+/// against the width of a form.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Displacement {
+    value: i32,
+}
+
+/// Represents the type of an Immediate
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub enum ImmediateType {
     UnsignedInteger,
     SignedInteger,
@@ -108,16 +156,193 @@ pub enum ImmediateType {
 }
 
 /// Represents an immediate value as a 64-bit value. Could be a float, signed or unsigned integer.
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+///
+/// Two immediates are equal when both their bytes and their types are. Immediates are ordered by type
+/// first and then by their value as that type, floats using the IEEE 754 total order.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub struct Immediate {
     bytes: [u8; Immediate::BYTES],
     tp: ImmediateType,
 }
 
+/// The error returned when the value of an immediate cannot be represented by the requested type,
+/// either because it is out of range or because a float was converted to an integer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ImmediateOutOfRange;
+
+/// This enum defines the conditions that can be tested against the flags register (rfl) and their
+/// value as a byte.
+///
+/// The compare instructions set the less than and greater than flags using their own interpretation
+/// of the operands (unsigned for `cmp`, signed for `cmpi` and floating point for `cmpf`). `cmp` and
+/// `cmpi` additionally set the unsigned less than and unsigned greater than flags by comparing the
+/// raw register values as unsigned integers, these are the flags tested by the unsigned conditions.
+///
+/// # Conditions
+/// 0000 - eq - Equal
+///
+/// 0001 - ne - Not equal
+///
+/// 0010 - lt - Less than
+///
+/// 0011 - le - Less than or equal
+///
+/// 0100 - gt - Greater than
+///
+/// 0101 - ge - Greater than or equal
+///
+/// 0110 - ltu - Unsigned less than
+///
+/// 0111 - leu - Unsigned less than or equal
+///
+/// 1000 - gtu - Unsigned greater than
+///
+/// 1001 - geu - Unsigned greater than or equal
+///
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Condition {
+    /// Equal
+    EQ = 0,
+    /// Not equal
+    NE = 1,
+    /// Less than
+    LT = 2,
+    /// Less than or equal
+    LE = 3,
+    /// Greater than
+    GT = 4,
+    /// Greater than or equal
+    GE = 5,
+
+    /// Unsigned less than
+    LTU = 6,
+    /// Unsigned less than or equal
+    LEU = 7,
+    /// Unsigned greater than
+    GTU = 8,
+    /// Unsigned greater than or equal
+    GEU = 9,
+}
+
+/// A set of registers, holding a bit for each register.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub struct RegisterSet {
+    bits: u16,
+}
+
+/// A single operand of an instruction, tagged with its kind.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Operand {
+    Register(Register),
+    Immediate(Immediate),
+    Address(Address),
+    Condition(Condition),
+    Displacement(Displacement),
+}
+
+impl OperandKind {
+    /// Returns the operand kind for a type code, or None if the code is unknown.
+    pub const fn from_type_code(code: u8) -> Option<OperandKind> {
+        return match code {
+            0 => Some(OperandKind::Register),
+            1 => Some(OperandKind::Immediate),
+            2 => Some(OperandKind::Address),
+            3 => Some(OperandKind::Condition),
+            4 => Some(OperandKind::Displacement),
+            _ => None,
+        };
+    }
+}
+
 impl Address {
-    /// Creates a new address from an absolute address.
+    /// Creates a new code address from an absolute address.
     pub fn new(absolute_address: u64) -> Self {
-        return Self { absolute_address };
+        return Self::code(absolute_address);
+    }
+
+    /// Creates the address of the instruction at an offset from the start of the program.
+    pub const fn code(offset: u64) -> Self {
+        return Self {
+            absolute_address: offset,
+            kind: AddressKind::Code,
+        };
+    }
+
+    /// Creates the address of heap allocated memory from its identifier.
+    pub const fn heap(handle: u64) -> Self {
+        return Self {
+            absolute_address: handle,
+            kind: AddressKind::Heap,
+        };
+    }
+
+    pub const fn kind(&self) -> AddressKind {
+        return self.kind;
+    }
+
+    /// The offset of the instruction or the identifier of the heap memory the address refers to.
+    pub const fn as_u64(&self) -> u64 {
+        return self.absolute_address;
+    }
+
+    pub const fn is_code(&self) -> bool {
+        return matches!(self.kind, AddressKind::Code);
+    }
+
+    pub fn set_kind(&mut self, kind: AddressKind) {
+        self.kind = kind;
+    }
+
+    pub fn with_kind(mut self, kind: AddressKind) -> Self {
+        self.set_kind(kind);
+
+        return self;
+    }
+}
+
+impl Displacement {
+    pub const fn new(value: i32) -> Self {
+        return Self { value };
+    }
+
+    /// The displacement from the end of a branch to a target, or None if it does not fit in 32
+    /// bits.
+    pub fn between(end: u64, target: u64) -> Option<Self> {
+        let value = (target as i128 - end as i128).try_into().ok()?;
+
+        return Some(Self::new(value));
+    }
+
+    /// Creates a displacement to encode in a number of bytes, returns None if it does not fit.
+    pub const fn with_width(value: i32, bytes: usize) -> Option<Self> {
+        let displacement = Self::new(value);
+
+        if displacement.min_bytes() > bytes {
+            return None;
+        }
+
+        return Some(displacement);
+    }
+
+    pub const fn value(&self) -> i32 {
+        return self.value;
+    }
+
+    /// The offset of the target of a branch ending at an offset.
+    pub const fn target(&self, end: u64) -> u64 {
+        return end.wrapping_add_signed(self.value as i64);
+    }
+
+    /// The smallest number of bytes the displacement can be encoded in, 1, 2 or 4.
+    pub const fn min_bytes(&self) -> usize {
+        if self.value as i8 as i32 == self.value {
+            return 1;
+        } else if self.value as i16 as i32 == self.value {
+            return 2;
+        } else {
+            return 4;
+        }
     }
 }
 
@@ -128,6 +353,114 @@ impl Register {
     }
 }
 
+impl RegisterSet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        return Self { bits: 0 };
+    }
+
+    /// Returns the set with a register added.
+    pub const fn with(self, register: Register) -> Self {
+        return Self {
+            bits: self.bits | (1 << register as u8),
+        };
+    }
+
+    pub fn insert(&mut self, register: Register) {
+        *self = self.with(register);
+    }
+
+    pub fn remove(&mut self, register: Register) {
+        self.bits &= !(1 << register as u8);
+    }
+
+    pub const fn contains(&self, register: Register) -> bool {
+        return self.bits & (1 << register as u8) != 0;
+    }
+
+    /// Returns the registers in either set.
+    pub const fn union(self, other: RegisterSet) -> Self {
+        return Self {
+            bits: self.bits | other.bits,
+        };
+    }
+
+    /// Returns the registers in both sets.
+    pub const fn intersection(self, other: RegisterSet) -> Self {
+        return Self {
+            bits: self.bits & other.bits,
+        };
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        return self.bits == 0;
+    }
+
+    pub const fn len(&self) -> usize {
+        return self.bits.count_ones() as usize;
+    }
+
+    /// Iterates over the registers in the set in the order of their values.
+    pub fn iter(&self) -> impl Iterator<Item = Register> {
+        let bits = self.bits;
+
+        return (0..16u8)
+            .filter(move |bit| bits & (1 << bit) != 0)
+            .map(Register::from_bits);
+    }
+}
+
+impl Operand {
+    /// Returns the kind of this operand.
+    pub const fn kind(&self) -> OperandKind {
+        return match self {
+            Operand::Register(_) => OperandKind::Register,
+            Operand::Immediate(_) => OperandKind::Immediate,
+            Operand::Address(_) => OperandKind::Address,
+            Operand::Condition(_) => OperandKind::Condition,
+            Operand::Displacement(_) => OperandKind::Displacement,
+        };
+    }
+}
+
+impl Condition {
+    /// The bit of the flags register set when the compared values are equal.
+    pub const EQUAL_FLAG: u64 = 0b0000_0001;
+    /// The bit of the flags register set when the first compared value is less than the second.
+    pub const LESS_FLAG: u64 = 0b0000_0010;
+    /// The bit of the flags register set when the first compared value is greater than the second.
+    pub const GREATER_FLAG: u64 = 0b0000_0100;
+    /// The bit of the flags register set when the first compared value is less than the second
+    /// when both are treated as unsigned integers.
+    pub const UNSIGNED_LESS_FLAG: u64 = 0b0000_1000;
+    /// The bit of the flags register set when the first compared value is greater than the second
+    /// when both are treated as unsigned integers.
+    pub const UNSIGNED_GREATER_FLAG: u64 = 0b0001_0000;
+
+    /// Returns the condition variant for the specified indicator.
+    pub fn from_bits(indicator: u8) -> Condition {
+        return indicator.into();
+    }
+
+    /// Returns true if this condition holds for the value of the flags register.
+    pub const fn evaluate(self, flags: u64) -> bool {
+        let equal = flags & Self::EQUAL_FLAG != 0;
+
+        return match self {
+            Condition::EQ => equal,
+            Condition::NE => !equal,
+            Condition::LT => flags & Self::LESS_FLAG != 0,
+            Condition::LE => equal || flags & Self::LESS_FLAG != 0,
+            Condition::GT => flags & Self::GREATER_FLAG != 0,
+            Condition::GE => equal || flags & Self::GREATER_FLAG != 0,
+            Condition::LTU => flags & Self::UNSIGNED_LESS_FLAG != 0,
+            Condition::LEU => equal || flags & Self::UNSIGNED_LESS_FLAG != 0,
+            Condition::GTU => flags & Self::UNSIGNED_GREATER_FLAG != 0,
+            Condition::GEU => equal || flags & Self::UNSIGNED_GREATER_FLAG != 0,
+        };
+    }
+}
+
 impl Immediate {
     /// Creates a new immediate from the constituent bytes.
     pub fn new(bytes: [u8; Immediate::BYTES]) -> Self {
@@ -146,14 +479,57 @@ impl Immediate {
 
         return self;
     }
+
+    pub const fn immediate_type(&self) -> ImmediateType {
+        return self.tp;
+    }
+
+    /// Reinterprets the bytes as an unsigned integer, regardless of the type.
+    pub const fn as_u64(&self) -> u64 {
+        return u64::from_le_bytes(self.bytes);
+    }
+
+    /// Reinterprets the bytes as a signed integer, regardless of the type.
+    pub const fn as_i64(&self) -> i64 {
+        return i64::from_le_bytes(self.bytes);
+    }
+
+    /// Reinterprets the bytes as a float, regardless of the type.
+    pub const fn as_f64(&self) -> f64 {
+        return f64::from_le_bytes(self.bytes);
+    }
+
+    /// The value as a byte, e.g. a shift count. Fails for floats and values out of range.
+    pub fn try_as_u8(&self) -> Result<u8, ImmediateOutOfRange> {
+        return u8::try_from(*self);
+    }
+
+    /// The value as a size or offset. Fails for floats and values out of range.
+    pub fn try_as_usize(&self) -> Result<usize, ImmediateOutOfRange> {
+        return usize::try_from(*self);
+    }
+
+    /// The integer value according to the type, None for floats.
+    const fn integer(&self) -> Option<i128> {
+        return match self.tp {
+            ImmediateType::UnsignedInteger => Some(self.as_u64() as i128),
+            ImmediateType::SignedInteger => Some(self.as_i64() as i128),
+            ImmediateType::Float => None,
+        };
+    }
 }
 
 impl InstructionArgument for Address {
     const BIT_SIZE: usize = 64;
+    const KIND: OperandKind = OperandKind::Address;
 
     fn into_bytes(self) -> Vec<u8> {
         return Into::<[u8; Self::BYTES]>::into(self).to_vec();
     }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        return Some(Self::new(u64::from_le_bytes(bytes.try_into().ok()?)));
+    }
 }
 
 impl Into<u64> for Address {
@@ -164,9 +540,7 @@ impl Into<u64> for Address {
 
 impl From<u64> for Address {
     fn from(n: u64) -> Self {
-        return Self {
-            absolute_address: n,
-        };
+        return Self::code(n);
     }
 }
 
@@ -188,12 +562,65 @@ impl fmt::Display for Address {
     }
 }
 
+impl InstructionArgument for Displacement {
+    const BIT_SIZE: usize = 32;
+    const KIND: OperandKind = OperandKind::Displacement;
+
+    fn into_bytes(self) -> Vec<u8> {
+        return Into::<[u8; Self::BYTES]>::into(self).to_vec();
+    }
+
+    /// Sign extends a displacement encoded in 1, 2 or 4 bytes.
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let value = match bytes.len() {
+            1 => i8::from_le_bytes(bytes.try_into().ok()?) as i32,
+            2 => i16::from_le_bytes(bytes.try_into().ok()?) as i32,
+            4 => i32::from_le_bytes(bytes.try_into().ok()?),
+            _ => return None,
+        };
+
+        return Some(Self::new(value));
+    }
+}
+
+impl Into<i32> for Displacement {
+    fn into(self) -> i32 {
+        return self.value;
+    }
+}
+
+impl From<i32> for Displacement {
+    fn from(value: i32) -> Self {
+        return Self::new(value);
+    }
+}
+
+impl Into<[u8; Displacement::BYTES]> for Displacement {
+    fn into(self) -> [u8; Displacement::BYTES] {
+        return self.value.to_le_bytes();
+    }
+}
+
+impl fmt::Display for Displacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "0d{}", self.value);
+    }
+}
+
 impl InstructionArgument for Register {
     const BIT_SIZE: usize = 4;
+    const KIND: OperandKind = OperandKind::Register;
 
     fn into_bytes(self) -> Vec<u8> {
         return vec![self as u8];
     }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        return match bytes {
+            [value] if *value <= Register::R9 as u8 => Some(Self::from_bits(*value)),
+            _ => None,
+        };
+    }
 }
 
 impl From<u8> for Register {
@@ -221,6 +648,12 @@ impl From<u8> for Register {
     }
 }
 
+impl FromIterator<Register> for RegisterSet {
+    fn from_iter<T: IntoIterator<Item = Register>>(iter: T) -> Self {
+        return iter.into_iter().fold(Self::new(), Self::with);
+    }
+}
+
 impl fmt::Display for Register {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
@@ -246,12 +679,71 @@ impl fmt::Display for Register {
     }
 }
 
+impl InstructionArgument for Condition {
+    const BIT_SIZE: usize = 4;
+    const KIND: OperandKind = OperandKind::Condition;
+
+    fn into_bytes(self) -> Vec<u8> {
+        return vec![self as u8];
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        return match bytes {
+            [value] if *value <= Condition::GEU as u8 => Some(Self::from_bits(*value)),
+            _ => None,
+        };
+    }
+}
+
+impl From<u8> for Condition {
+    fn from(value: u8) -> Self {
+        return match value {
+            0 => Self::EQ,
+            1 => Self::NE,
+            2 => Self::LT,
+            3 => Self::LE,
+            4 => Self::GT,
+            5 => Self::GE,
+
+            6 => Self::LTU,
+            7 => Self::LEU,
+            8 => Self::GTU,
+            9 => Self::GEU,
+            _ => panic!("Unknown condition {}", value),
+        };
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Condition::EQ => "eq",
+            Condition::NE => "ne",
+            Condition::LT => "lt",
+            Condition::LE => "le",
+            Condition::GT => "gt",
+            Condition::GE => "ge",
+            Condition::LTU => "ltu",
+            Condition::LEU => "leu",
+            Condition::GTU => "gtu",
+            Condition::GEU => "geu",
+        };
+
+        return write!(f, "{}", name);
+    }
+}
+
 impl InstructionArgument for Immediate {
     const BIT_SIZE: usize = 64;
+    const KIND: OperandKind = OperandKind::Immediate;
 
     fn into_bytes(self) -> Vec<u8> {
         return Into::<[u8; Self::BYTES]>::into(self).to_vec();
     }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        return Some(Self::new(bytes.try_into().ok()?));
+    }
 }
 
 impl From<[u8; Immediate::BYTES]> for Immediate {
@@ -266,12 +758,6 @@ impl From<i64> for Immediate {
     }
 }
 
-impl Into<u64> for Immediate {
-    fn into(self) -> u64 {
-        return u64::from_le_bytes(self.bytes);
-    }
-}
-
 impl From<u64> for Immediate {
     fn from(n: u64) -> Self {
         return Self::new(n.to_le_bytes()).with_type(ImmediateType::UnsignedInteger);
@@ -290,18 +776,151 @@ impl From<u8> for Immediate {
     }
 }
 
+impl From<u16> for Immediate {
+    fn from(n: u16) -> Self {
+        return Self::from(n as u64);
+    }
+}
+
+impl From<u32> for Immediate {
+    fn from(n: u32) -> Self {
+        return Self::from(n as u64);
+    }
+}
+
+impl From<i8> for Immediate {
+    fn from(n: i8) -> Self {
+        return Self::from(n as i64);
+    }
+}
+
+impl From<i16> for Immediate {
+    fn from(n: i16) -> Self {
+        return Self::from(n as i64);
+    }
+}
+
+impl From<i32> for Immediate {
+    fn from(n: i32) -> Self {
+        return Self::from(n as i64);
+    }
+}
+
+impl From<f32> for Immediate {
+    fn from(n: f32) -> Self {
+        return Self::from(n as f64);
+    }
+}
+
+/// Implements the checked conversion of an immediate to an integer type, using the value of the
+/// immediate according to its type.
+macro_rules! impl_try_from_immediate {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<Immediate> for $t {
+                type Error = ImmediateOutOfRange;
+
+                fn try_from(immediate: Immediate) -> Result<Self, Self::Error> {
+                    return immediate
+                        .integer()
+                        .and_then(|value| <$t>::try_from(value).ok())
+                        .ok_or(ImmediateOutOfRange);
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_immediate!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl TryFrom<Immediate> for f64 {
+    type Error = ImmediateOutOfRange;
+
+    /// Only float immediates can be converted, use `as_f64` to reinterpret other immediates.
+    fn try_from(immediate: Immediate) -> Result<Self, Self::Error> {
+        return match immediate.tp {
+            ImmediateType::Float => Ok(immediate.as_f64()),
+            _ => Err(ImmediateOutOfRange),
+        };
+    }
+}
+
+impl PartialOrd for Immediate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for Immediate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.tp.cmp(&other.tp).then_with(|| match self.tp {
+            ImmediateType::UnsignedInteger => self.as_u64().cmp(&other.as_u64()),
+            ImmediateType::SignedInteger => self.as_i64().cmp(&other.as_i64()),
+            ImmediateType::Float => self.as_f64().total_cmp(&other.as_f64()),
+        });
+    }
+}
+
 impl Into<[u8; Immediate::BYTES]> for Immediate {
     fn into(self) -> [u8; Immediate::BYTES] {
-        return Into::<u64>::into(self).to_le_bytes();
+        return self.bytes;
     }
 }
 
 impl fmt::Display for Immediate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         return match self.tp {
-            ImmediateType::UnsignedInteger => write!(f, "0u{}", u64::from_le_bytes(self.bytes)),
-            ImmediateType::SignedInteger => write!(f, "0i{}", i64::from_le_bytes(self.bytes)),
-            ImmediateType::Float => write!(f, "0f{}", f64::from_le_bytes(self.bytes)),
+            ImmediateType::UnsignedInteger => write!(f, "0u{}", self.as_u64()),
+            ImmediateType::SignedInteger => write!(f, "0i{}", self.as_i64()),
+            ImmediateType::Float => write!(f, "0f{}", self.as_f64()),
+        };
+    }
+}
+
+impl fmt::Display for ImmediateOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "immediate out of range");
+    }
+}
+
+impl From<Register> for Operand {
+    fn from(register: Register) -> Self {
+        return Operand::Register(register);
+    }
+}
+
+impl From<Immediate> for Operand {
+    fn from(immediate: Immediate) -> Self {
+        return Operand::Immediate(immediate);
+    }
+}
+
+impl From<Address> for Operand {
+    fn from(address: Address) -> Self {
+        return Operand::Address(address);
+    }
+}
+
+impl From<Condition> for Operand {
+    fn from(condition: Condition) -> Self {
+        return Operand::Condition(condition);
+    }
+}
+
+impl From<Displacement> for Operand {
+    fn from(displacement: Displacement) -> Self {
+        return Operand::Displacement(displacement);
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Operand::Register(register) => write!(f, "{}", register),
+            Operand::Immediate(immediate) => write!(f, "{}", immediate),
+            Operand::Address(address) => write!(f, "{}", address),
+            Operand::Condition(condition) => write!(f, "{}", condition),
+            Operand::Displacement(displacement) => write!(f, "{}", displacement),
         };
     }
 }
@@ -309,6 +928,7 @@ impl fmt::Display for Immediate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::byte_sink::EncodeError;
     use crate::instruction::Instruction;
     use alloc::vec;
     use alloc::vec::Vec;
@@ -402,4 +1022,188 @@ mod tests {
         vec![0x37, 5, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0],
         test_jmp_into_bytes
     );
+
+    generate_test!(
+        Instruction::Jmp8(Displacement::new(-3)),
+        vec![0xff, 0x0, 0xfd],
+        test_jmp8_into_bytes
+    );
+
+    #[test]
+    pub fn test_jmp8_displacement_too_wide() {
+        let instruction = Instruction::Jmp8(Displacement::new(300));
+        let mut bytes = Vec::new();
+
+        assert_eq!(
+            instruction.encode_to(&mut bytes),
+            Err(EncodeError::DisplacementOutOfRange(Displacement::new(300)))
+        );
+        assert_eq!(
+            instruction.encode_compact_to(&mut bytes),
+            Err(EncodeError::DisplacementOutOfRange(Displacement::new(300)))
+        );
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    pub fn test_displacement_with_width() {
+        assert_eq!(
+            Displacement::with_width(-128, 1),
+            Some(Displacement::new(-128))
+        );
+        assert_eq!(Displacement::with_width(300, 1), None);
+        assert_eq!(
+            Displacement::with_width(300, 2),
+            Some(Displacement::new(300))
+        );
+    }
+
+    generate_test!(
+        Instruction::Setcc(Condition::LT, Register::R0),
+        vec![0x46, 0b0010_0110],
+        test_setcc_into_bytes
+    );
+
+    generate_test!(
+        Instruction::Csel(Condition::GEU, Register::R0, Register::R1, Register::R2),
+        vec![0x47, 0b1001_0110, 0b0111_1000],
+        test_csel_into_bytes
+    );
+
+    generate_test!(
+        Instruction::Enter(Immediate::from(2u64)),
+        vec![0x48, 0x2, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0],
+        test_enter_into_bytes
+    );
+
+    #[test]
+    pub fn test_from_bytes() {
+        let instructions = [
+            Instruction::Nop,
+            Instruction::Ldi(Immediate::from(-12i64), Register::R3),
+            Instruction::Copy(
+                Register::R5,
+                Register::R0,
+                Register::R6,
+                Register::R1,
+                Register::R7,
+            ),
+            Instruction::Csel(Condition::GEU, Register::R0, Register::R1, Register::R2),
+            Instruction::Swpa(Address::heap(12), Address::heap(600)),
+            Instruction::Freea(Address::heap(3)),
+            Instruction::Call(Address::code(40)),
+            Instruction::Jlt16(Displacement::new(-300)),
+            Instruction::Call32(Displacement::new(70000)),
+            Instruction::Ldf(Immediate::from(12.22), Register::R0),
+            Instruction::Copyi(
+                Immediate::from(0x45u64),
+                Immediate::from(0x12u64),
+                Immediate::from(0x33u64),
+                Register::R0,
+                Register::R1,
+            ),
+        ];
+
+        for instruction in instructions {
+            let mut bytes: Vec<u8> = instruction.into();
+            let length = bytes.len();
+
+            // Trailing bytes belong to the next instruction and should be left alone.
+            bytes.push(0xff);
+
+            let (decoded, consumed) = Instruction::from_bytes(&bytes).unwrap();
+
+            assert_eq!(consumed, length);
+            assert_eq!(decoded, instruction);
+            assert_eq!(decoded.into_assembly(), instruction.into_assembly());
+        }
+    }
+
+    #[test]
+    pub fn test_from_bytes_invalid() {
+        // Truncated immediate
+        assert_eq!(Instruction::from_bytes(&[0x1, 0x34, 0x0]), None);
+        // Condition out of range
+        assert_eq!(Instruction::from_bytes(&[0x46, 0b1111_0110]), None);
+        // Unknown opcode
+        assert_eq!(Instruction::from_bytes(&[0xfe]), None);
+        assert_eq!(Instruction::from_bytes(&[]), None);
+    }
+
+    #[test]
+    pub fn test_immediate_conversions() {
+        let shift = Immediate::from(3u32);
+        let offset = Immediate::from(-8i32);
+        let float = Immediate::from(1.5f32);
+
+        assert_eq!(shift.try_as_u8(), Ok(3));
+        assert_eq!(offset.as_i64(), -8);
+        assert_eq!(offset.try_as_usize(), Err(ImmediateOutOfRange));
+        assert_eq!(i8::try_from(offset), Ok(-8));
+        assert_eq!(
+            Immediate::from(300u16).try_as_u8(),
+            Err(ImmediateOutOfRange)
+        );
+        assert_eq!(float.as_f64(), 1.5);
+        assert_eq!(f64::try_from(float), Ok(1.5));
+        assert_eq!(f64::try_from(shift), Err(ImmediateOutOfRange));
+        assert_eq!(u32::try_from(float), Err(ImmediateOutOfRange));
+        assert_eq!(u64::try_from(float), Err(ImmediateOutOfRange));
+        assert_eq!(u64::try_from(offset), Err(ImmediateOutOfRange));
+        assert_eq!(u64::try_from(Immediate::from(u64::MAX)), Ok(u64::MAX));
+        assert_eq!(float.immediate_type(), ImmediateType::Float);
+
+        let mut sorted = vec![
+            shift,
+            float,
+            offset,
+            Immediate::from(-20i64),
+            Immediate::from(1u8),
+        ];
+        sorted.sort();
+
+        assert_eq!(
+            sorted,
+            vec![
+                Immediate::from(1u8),
+                shift,
+                Immediate::from(-20i64),
+                offset,
+                float
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_condition_evaluate() {
+        let less = Condition::LESS_FLAG | Condition::UNSIGNED_GREATER_FLAG;
+
+        assert!(Condition::LT.evaluate(less));
+        assert!(Condition::LE.evaluate(less));
+        assert!(Condition::NE.evaluate(less));
+        assert!(Condition::GTU.evaluate(less));
+        assert!(!Condition::GE.evaluate(less));
+        assert!(!Condition::LTU.evaluate(less));
+        assert!(Condition::GEU.evaluate(Condition::EQUAL_FLAG));
+        assert!(!Condition::NE.evaluate(Condition::EQUAL_FLAG));
+    }
+
+    #[test]
+    pub fn test_register_set() {
+        let mut set: RegisterSet = [Register::R3, Register::RSP].into_iter().collect();
+
+        set.insert(Register::R9);
+        set.remove(Register::R3);
+
+        assert!(set.contains(Register::RSP));
+        assert!(!set.contains(Register::R3));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<Register>>(),
+            vec![Register::RSP, Register::R9]
+        );
+        assert!(set
+            .intersection(RegisterSet::new().with(Register::R0))
+            .is_empty());
+    }
 }
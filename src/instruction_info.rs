@@ -0,0 +1,147 @@
+use crate::extension::Extension;
+use crate::instruction::{Opcode, INSTRUCTIONS};
+use crate::instruction_arguments::OperandKind;
+
+/// Describes a single instruction of the instruction set, the entries are generated from the
+/// instruction set table and can be found in `INSTRUCTIONS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionInfo {
+    mnemonic: &'static str,
+    description: &'static str,
+    opcode: u16,
+    encoded_len: usize,
+    operands: &'static [OperandKind],
+    friendly_ordering: &'static [OperandKind],
+    extension: Option<Extension>,
+}
+
+impl InstructionInfo {
+    pub const fn new(
+        mnemonic: &'static str,
+        description: &'static str,
+        opcode: u16,
+        encoded_len: usize,
+        operands: &'static [OperandKind],
+        friendly_ordering: &'static [OperandKind],
+        extension: Option<Extension>,
+    ) -> Self {
+        return Self {
+            mnemonic,
+            description,
+            opcode,
+            encoded_len,
+            operands,
+            friendly_ordering,
+            extension,
+        };
+    }
+
+    /// Returns the description of the instruction with an opcode.
+    pub fn for_opcode(opcode: Opcode) -> &'static Self {
+        return opcode.info();
+    }
+
+    /// Returns the description of the instruction with a mnemonic, e.g. "copyi".
+    pub fn for_mnemonic(mnemonic: &str) -> Option<&'static Self> {
+        return INSTRUCTIONS.iter().find(|info| info.mnemonic == mnemonic);
+    }
+
+    /// The name of the instruction used in assembly.
+    pub const fn mnemonic(&self) -> &'static str {
+        return self.mnemonic;
+    }
+
+    pub const fn description(&self) -> &'static str {
+        return self.description;
+    }
+
+    /// The value of the opcode of the instruction.
+    pub const fn opcode(&self) -> u16 {
+        return self.opcode;
+    }
+
+    /// The number of bytes the instruction takes up once encoded, including the opcode.
+    pub const fn encoded_len(&self) -> usize {
+        return self.encoded_len;
+    }
+
+    /// The kinds of the operands in the order they are held by the instruction and encoded.
+    pub const fn operands(&self) -> &'static [OperandKind] {
+        return self.operands;
+    }
+
+    /// The kinds of the operands in the order they are written in assembly.
+    pub const fn friendly_ordering(&self) -> &'static [OperandKind] {
+        return self.friendly_ordering;
+    }
+
+    /// The extension declaring the instruction, None for instructions of the base set.
+    pub const fn extension(&self) -> Option<Extension> {
+        return self.extension;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::Instruction;
+    use crate::instruction_arguments::{Address, Condition, Immediate, Register, RegisterSet};
+    use alloc::vec::Vec;
+
+    #[test]
+    pub fn test_lookup() {
+        let info = InstructionInfo::for_mnemonic("csel").unwrap();
+
+        assert_eq!(
+            InstructionInfo::for_opcode(Opcode::try_from(info.opcode()).unwrap()),
+            info
+        );
+        assert_eq!(
+            info.friendly_ordering(),
+            &[
+                OperandKind::Register,
+                OperandKind::Register,
+                OperandKind::Register,
+                OperandKind::Condition
+            ]
+        );
+        assert_eq!(InstructionInfo::for_mnemonic("csell"), None);
+    }
+
+    #[test]
+    pub fn test_encoded_len() {
+        let instructions = [
+            Instruction::Nop,
+            Instruction::Brk(Immediate::from(1u64)),
+            Instruction::Csel(Condition::EQ, Register::R0, Register::R1, Register::R2),
+        ];
+
+        for instruction in instructions {
+            let bytes: Vec<u8> = instruction.into();
+
+            assert_eq!(instruction.info().encoded_len(), bytes.len());
+        }
+    }
+
+    #[test]
+    pub fn test_semantics() {
+        let csel = Instruction::Csel(Condition::EQ, Register::R0, Register::R1, Register::R2);
+
+        assert_eq!(
+            csel.reads(),
+            RegisterSet::new()
+                .with(Register::R1)
+                .with(Register::R2)
+                .with(Register::RFL)
+        );
+        assert_eq!(csel.writes(), RegisterSet::new().with(Register::R0));
+        assert!(!csel.affects_flags());
+        assert!(Instruction::Cmpi(Register::R0, Register::R1).affects_flags());
+
+        assert!(Instruction::Ret.is_branch() && Instruction::Ret.is_terminator());
+        assert!(!Instruction::Call(Address::new(0)).is_terminator());
+        assert!(Instruction::Free(Register::R0).touches_heap());
+        assert!(Instruction::Divu(Register::R0, Register::R1, Register::R2).may_trap());
+        assert!(!Instruction::Addu(Register::R0, Register::R1, Register::R2).may_trap());
+    }
+}
@@ -1,12 +1,110 @@
 #![no_std]
+// Explicit returns and `Into` implementations are used throughout the crate.
+#![allow(clippy::needless_return, clippy::from_over_into)]
 extern crate alloc;
 
+#[cfg(feature = "macros")]
+pub use vxl_iset_macros::vxl_isa;
+
+/// Assembles programs from their text form.
+pub mod assembler;
+/// Supports debuggers patching breakpoints into a program.
+pub mod breakpoint;
+/// Defines the destinations instructions can be encoded into.
+pub mod byte_sink;
+/// Maps the instructions of a program back to its source.
+pub mod debug_info;
+/// Prints programs in the syntax read by the assembler.
+pub mod disassembler;
+/// Defines the encodings programs can be stored in.
+///
+/// The fixed encoding gives every immediate and address 8 bytes so `ldb $r0, 0u1` takes up 10
+/// bytes. The compact encoding, selected by version 1 of the vxl format, writes integer immediates
+/// and addresses as LEB128 instead, bringing it down to 3 bytes. Typical programs, which mostly use
+/// small constants, shrink to between 40% and 65% of their fixed size. Programs in the
+/// compact encoding are produced by `assembler::assemble_with` and decoded by
+/// `program::Program::predecode_with`, or `vxl_file::VXLFile::program` which picks the encoding
+/// from the header.
+pub mod encoding;
+/// Reference implementations of the arithmetic and bitwise instructions, shared by interpreters and
+/// constant folding.
+///
+/// The float extension instructions that need a math library, `sqrtf`, `floorf`, `ceilf`, `roundf`
+/// and `fmaf`, are not provided as they are not available without std.
+pub mod eval;
+/// Defines the model for exceptions thrown by `throw` and faults raised by the vm.
+pub mod exception;
 /// Defines a trait that allows for the execution of arbitrary instructions
+///
+/// # Stack frames
+/// The stack holds 64-bit values and rsp holds the number of values currently on the stack. Stack
+/// slot `0` holds the oldest value on the stack and slot `rsp - 1` the most recently pushed one.
+///
+/// `call` pushes the offset of the instruction following it before jumping and `ret` pops that
+/// offset and continues from it. `enter n` then pushes rfp, copies rsp into rfp and pushes `n`
+/// zeroed values. `leave` undoes this by copying rfp into rsp and popping rfp, so a function is
+/// expected to execute `leave` before `ret`.
+///
+/// `ldfp` and `stfp` access stack slot `rfp + i` for a signed integer offset `i`. Within a frame
+/// created by `enter n` offsets `0` to `n - 1` are the locals, `-1` is the saved frame pointer, `-2`
+/// is the return offset pushed by `call` and `-3` onwards are the values pushed by the caller
+/// before `call`, starting with the last one pushed. Accessing a slot at or past rsp is an error.
 pub mod execute_instruction;
+/// Defines the optional extensions to the instruction set.
+pub mod extension;
 /// Defines the format of an instruction
+///
+/// # Operands
+/// Instructions writing to a register operand take it as their first operand in assembly, e.g.
+/// `addi $r0, $r1, $r2` writes the sum of r1 and r2 into r0. The registers each instruction reads
+/// and writes, including those that are not operands, are given by `Instruction::reads` and
+/// `Instruction::writes`.
+///
+/// Immediates are encoded without their `ImmediateType`. Decoding gives each immediate the type
+/// declared for it in the instruction set, e.g. `ldf` takes a float and `ldi` a signed integer, so
+/// instructions built with those types decode to themselves.
+///
+/// Addresses are likewise encoded without their `AddressKind`. Branches and `call` take code
+/// addresses, the offset of an instruction, while `freea` and `swpa` take heap handles, so only the
+/// former need relocating when code moves.
+///
+/// # Branches
+/// Besides their absolute form taking a code address, `jmp`, the conditional jumps and `call` have
+/// relative forms named after the width of their `Displacement`, e.g. `jmp8`, `jeq16` and `call32`.
+/// The target of a relative branch is the offset of the instruction following it plus the
+/// displacement, so it stays valid wherever the code holding both is placed.
+///
+/// # Rewriting
+/// `Instruction::operands` lists the operands of an instruction in assembly order and the `map_*`
+/// methods rebuild an instruction with every operand of one kind replaced, e.g. renaming registers
+/// is `instruction.map_registers(|register| allocation[register as usize])`. Passes that treat a few
+/// instructions specially can implement `InstructionVisitor`, overriding only the methods they need.
 pub mod instruction;
 /// Defines the arguments that can be used for instructions.
 pub mod instruction_arguments;
+/// Describes the instructions of the instruction set at runtime.
+pub mod instruction_info;
+/// Combines relocatable objects into vxl files.
+pub mod linker;
+/// Defines relocatable objects, compiled separately and combined by the linker.
+pub mod object;
+/// Defines how the opcodes of instructions are encoded.
+pub mod opcode;
+/// Decodes whole programs ahead of time for interpreters.
+pub mod program;
+/// Defines the sections of a vxl file and how they are loaded.
+pub mod section;
+/// Defines the symbol table of a vxl file, naming offsets in its sections.
+pub mod symbol;
 pub mod syscall_handler;
 /// Structs for defining the format for a vxl binary.
 pub mod vxl_file;
+
+/// Items used by generated code, this is not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
@@ -0,0 +1,166 @@
+use crate::debug_info::{LineRow, LineTable};
+use crate::encoding::InstructionEncoding;
+use crate::instruction_arguments::Address;
+use crate::object::Object;
+use crate::program::{DecodeError, Program};
+use crate::symbol::{Symbol, SymbolTable};
+use crate::vxl_file::{VXLBuilder, VXLFile};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The error returned when objects cannot be linked.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LinkError {
+    /// No object defines a symbol an object refers to.
+    UndefinedSymbol(String),
+    /// More than one object defines a global symbol.
+    DuplicateSymbol(String),
+    /// The code of an object does not hold valid instructions.
+    Decode(DecodeError),
+    /// A relocation of an object does not refer to a code address operand of an instruction, or
+    /// names a symbol of the object that is not in its code.
+    InvalidRelocation { object: usize, offset: u64 },
+}
+
+/// Links objects into a vxl file holding their code, symbols and line tables.
+///
+/// The code of the objects is laid out one after the other in the order given and the program
+/// starts at the first instruction of the first object. Relocations are resolved against the
+/// symbols of their own object first, then against the global symbols of every object, so local
+/// symbols of different objects may share a name while global ones must be defined once. Relative branches
+/// need no patching as the code of an object stays together.
+pub fn link(objects: &[Object]) -> Result<VXLFile, LinkError> {
+    let mut bases = Vec::with_capacity(objects.len());
+    let mut base = 0;
+
+    for object in objects {
+        bases.push(base);
+        base += object.code().len() as u64;
+    }
+
+    let mut globals = BTreeMap::new();
+
+    for (object, base) in objects.iter().zip(&bases) {
+        for symbol in object.symbols().symbols() {
+            if symbol.is_global()
+                && symbol.kind().is_code()
+                && globals
+                    .insert(symbol.name(), base + symbol.offset())
+                    .is_some()
+            {
+                return Err(LinkError::DuplicateSymbol(String::from(symbol.name())));
+            }
+        }
+    }
+
+    let mut code = Vec::new();
+    let mut symbols = Vec::new();
+    let mut lines = LineTable::new();
+
+    for (index, (object, base)) in objects.iter().zip(&bases).enumerate() {
+        let program = Program::predecode(object.code())?;
+        let start = code.len();
+
+        code.extend_from_slice(object.code());
+
+        for relocation in object.relocations() {
+            let invalid = LinkError::InvalidRelocation {
+                object: index,
+                offset: relocation.offset(),
+            };
+            // Only symbols in the code, the first section of an object, can be branched to.
+            let target = match object.symbols().symbol_by_name(relocation.symbol()) {
+                Some(symbol) if symbol.kind().is_code() && symbol.section() == 0 => {
+                    base + symbol.offset()
+                }
+                Some(_) => return Err(invalid),
+                None => *globals
+                    .get(relocation.symbol())
+                    .ok_or_else(|| LinkError::UndefinedSymbol(String::from(relocation.symbol())))?,
+            };
+            let instruction = program
+                .index_of(relocation.offset())
+                .and_then(|index| program.get(index))
+                .ok_or(invalid.clone())?;
+            let mut operand = 0;
+            let mut is_code = false;
+            let patched = instruction.map_addresses(|address| {
+                operand += 1;
+
+                if operand - 1 == relocation.operand() as usize {
+                    is_code = address.is_code();
+
+                    Address::code(target)
+                } else {
+                    address
+                }
+            });
+
+            if !is_code {
+                return Err(invalid);
+            }
+
+            let offset = start + relocation.offset() as usize;
+            let bytes: Vec<u8> = patched.into();
+
+            code[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        symbols.extend(object.symbols().symbols().iter().filter_map(|symbol| {
+            Symbol::new(
+                symbol.name(),
+                symbol.kind(),
+                0,
+                base + symbol.offset(),
+                symbol.size(),
+            )
+            .map(|placed| placed.with_binding(symbol.binding()))
+        }));
+
+        for row in object.lines().rows() {
+            let file = lines.file_index(&object.lines().files()[row.file() as usize]);
+
+            lines.insert(LineRow::new(
+                base + row.offset(),
+                file,
+                row.line(),
+                row.column(),
+            ));
+        }
+    }
+
+    let builder = VXLBuilder::new()
+        .code("code", code, InstructionEncoding::Fixed)
+        .symbols("symbols", &SymbolTable::new(symbols));
+
+    if lines.is_empty() {
+        return Ok(builder.build());
+    }
+
+    return Ok(builder.line_table(&lines).build());
+}
+
+impl From<DecodeError> for LinkError {
+    fn from(e: DecodeError) -> Self {
+        return LinkError::Decode(e);
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            LinkError::UndefinedSymbol(symbol) => write!(f, "undefined symbol {}", symbol),
+            LinkError::DuplicateSymbol(symbol) => write!(f, "symbol {} is defined twice", symbol),
+            LinkError::Decode(e) => write!(f, "{}", e),
+            LinkError::InvalidRelocation { object, offset } => {
+                write!(
+                    f,
+                    "invalid relocation at offset {} of object {}",
+                    offset, object
+                )
+            }
+        };
+    }
+}
@@ -0,0 +1,253 @@
+use crate::debug_info::LineTable;
+use crate::encoding::InstructionEncoding;
+use crate::section::SectionKind;
+use crate::symbol::{Symbol, SymbolTable};
+use crate::vxl_file::{LoadError, VXLBuilder, VXLFile, VXLHeader};
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A relocatable object, the unit `linker::link` combines into a vxl file. Objects are stored in
+/// `.vxo` files.
+///
+/// The code is in the fixed encoding and assumes it starts at offset 0. Every address operand
+/// naming a symbol has a relocation so the linker can patch it once the code is placed, symbols not
+/// defined by the object being resolved against the functions of the other objects.
+///
+/// # Representation when in binary
+/// Byte Offset - Value
+///
+/// 0x0 - Magic bytes (0x65, 0x58, 0x56, 0x4f)
+///
+/// 0x4 - Object version (0)
+///
+/// 0x5 - A section table and the contents of its sections laid out like the contents of a vxl
+/// file of version 2: the code, the symbols, the relocations and optionally the line table.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Object {
+    code: Vec<u8>,
+    symbols: SymbolTable,
+    relocations: Vec<Relocation>,
+    lines: LineTable,
+}
+
+/// The error returned when an object cannot be read.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ObjectError {
+    /// The object does not start with `Object::MAGIC` followed by `Object::VERSION`.
+    InvalidMagic,
+    /// The relocation section is truncated or holds an invalid relocation.
+    InvalidRelocations,
+    /// The sections of the object could not be read.
+    Load(LoadError),
+}
+
+/// An address operand to patch with the offset of a symbol once the code is placed.
+///
+/// # Representation when in binary
+/// The contents of a relocation section are the number of relocations (Little endian, 4 bytes)
+/// followed by each relocation:
+///
+/// 0x0 - Offset of the instruction (Little endian)
+///
+/// 0x8 - Index of the operand among the address operands of the instruction
+///
+/// 0x9 - Length of the name of the symbol in bytes
+///
+/// 0xa - Name of the symbol (UTF-8)
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Relocation {
+    offset: u64,
+    operand: u8,
+    symbol: String,
+}
+
+impl Object {
+    /// The magic bytes: 0x65, 0x58, 0x56, 0x4f.
+    pub const MAGIC: [u8; 4] = [0x65, 0x58, 0x56, 0x4f];
+    /// The version of the object format.
+    pub const VERSION: u8 = 0x0;
+
+    pub fn new(
+        code: Vec<u8>,
+        symbols: SymbolTable,
+        relocations: Vec<Relocation>,
+        lines: LineTable,
+    ) -> Self {
+        return Self {
+            code,
+            symbols,
+            relocations,
+            lines,
+        };
+    }
+
+    /// Reads an object, returns `ObjectError::InvalidMagic` if the magic bytes or version do not
+    /// match.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ObjectError> {
+        if bytes.get(..4) != Some(&Self::MAGIC) || bytes.get(4) != Some(&Self::VERSION) {
+            return Err(ObjectError::InvalidMagic);
+        }
+
+        let contents = bytes[5..].to_vec();
+        let header = VXLHeader::new(
+            VXLHeader::SECTIONED_VERSION,
+            contents.len() as u64,
+            0,
+            0,
+            [0; VXLHeader::HEADER_CHECKSUM_SIZE],
+        );
+        let file = VXLFile::new(header, contents);
+        let sections = file.sections()?;
+        let section = |kind: SectionKind| sections.iter().find(|section| section.kind() == kind);
+        let code = section(SectionKind::Code).ok_or(LoadError::MissingCode)?;
+        let relocations = match section(SectionKind::Relocations) {
+            Some(section) => Relocation::list_from_bytes(file.section_data(section))
+                .ok_or(ObjectError::InvalidRelocations)?,
+            None => Vec::new(),
+        };
+
+        return Ok(Self {
+            code: file.section_data(code).to_vec(),
+            symbols: file.symbols()?,
+            relocations,
+            lines: file.line_table()?,
+        });
+    }
+
+    /// The code in the fixed encoding.
+    pub fn code(&self) -> &[u8] {
+        return &self.code;
+    }
+
+    /// The symbols defined by the object.
+    pub fn symbols(&self) -> &SymbolTable {
+        return &self.symbols;
+    }
+
+    pub fn relocations(&self) -> &[Relocation] {
+        return &self.relocations;
+    }
+
+    pub fn lines(&self) -> &LineTable {
+        return &self.lines;
+    }
+
+    /// The symbols the object refers to without defining them.
+    pub fn undefined_symbols(&self) -> BTreeSet<&str> {
+        return self
+            .relocations
+            .iter()
+            .map(|relocation| relocation.symbol())
+            .filter(|symbol| self.symbols.symbol_by_name(symbol).is_none())
+            .collect();
+    }
+}
+
+impl Relocation {
+    /// Creates a new relocation, returns None if the name of the symbol is longer than
+    /// `Symbol::MAX_NAME_LEN` bytes.
+    pub fn new(offset: u64, operand: u8, symbol: &str) -> Option<Self> {
+        if symbol.len() > Symbol::MAX_NAME_LEN {
+            return None;
+        }
+
+        return Some(Self {
+            offset,
+            operand,
+            symbol: String::from(symbol),
+        });
+    }
+
+    /// The offset of the instruction holding the address.
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+
+    /// The index of the address among the address operands of the instruction.
+    pub fn operand(&self) -> u8 {
+        return self.operand;
+    }
+
+    /// The name of the symbol whose offset the address is patched with.
+    pub fn symbol(&self) -> &str {
+        return &self.symbol;
+    }
+
+    /// Reads the contents of a relocation section, returns None if they are truncated or invalid.
+    pub fn list_from_bytes(bytes: &[u8]) -> Option<Vec<Self>> {
+        let count = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+        let mut relocations = Vec::new();
+        let mut offset = 4;
+
+        for _ in 0..count {
+            let entry = bytes.get(offset..offset + 10)?;
+            let name_len = entry[9] as usize;
+            let name = bytes.get(offset + 10..offset + 10 + name_len)?;
+
+            relocations.push(Self {
+                offset: u64::from_le_bytes(entry[..8].try_into().ok()?),
+                operand: entry[8],
+                symbol: String::from(core::str::from_utf8(name).ok()?),
+            });
+            offset += 10 + name_len;
+        }
+
+        return Some(relocations);
+    }
+
+    /// Writes the contents of a relocation section.
+    pub fn list_to_bytes(relocations: &[Self]) -> Vec<u8> {
+        let mut bytes = (relocations.len() as u32).to_le_bytes().to_vec();
+
+        for relocation in relocations {
+            bytes.extend_from_slice(&relocation.offset.to_le_bytes());
+            bytes.push(relocation.operand);
+            bytes.push(relocation.symbol.len() as u8);
+            bytes.extend_from_slice(relocation.symbol.as_bytes());
+        }
+
+        return bytes;
+    }
+}
+
+impl From<LoadError> for ObjectError {
+    fn from(e: LoadError) -> Self {
+        return ObjectError::Load(e);
+    }
+}
+
+impl fmt::Display for ObjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            ObjectError::InvalidMagic => write!(f, "not a vxl object"),
+            ObjectError::InvalidRelocations => write!(f, "invalid relocations"),
+            ObjectError::Load(e) => write!(f, "{}", e),
+        };
+    }
+}
+
+impl Into<Vec<u8>> for &Object {
+    fn into(self) -> Vec<u8> {
+        let mut builder = VXLBuilder::new()
+            .code("code", self.code.clone(), InstructionEncoding::Fixed)
+            .symbols("symbols", &self.symbols)
+            .section(
+                "relocations",
+                SectionKind::Relocations,
+                Relocation::list_to_bytes(&self.relocations),
+            );
+
+        if !self.lines.is_empty() {
+            builder = builder.line_table(&self.lines);
+        }
+
+        let mut bytes = Object::MAGIC.to_vec();
+
+        bytes.push(Object::VERSION);
+        bytes.extend_from_slice(builder.build().contents());
+
+        return bytes;
+    }
+}
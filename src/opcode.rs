@@ -0,0 +1,87 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The byte prefixing two byte opcodes.
+pub const ESCAPE: u8 = 0xff;
+/// The first opcode encoded using the escape prefix.
+pub const FIRST_ESCAPED: u16 = 0xff00;
+
+/// The error returned when converting a value or a name that is not an opcode into an `Opcode`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UnknownOpcode;
+
+/// True if an opcode can be encoded.
+///
+/// # Encoding
+/// Opcodes `0x00` to `0xfe` are encoded as a single byte. The byte `0xff` is an escape prefix,
+/// opcodes `0xff00` to `0xffff` are encoded as the prefix followed by their low byte. Every other
+/// value is not a valid opcode.
+pub const fn is_valid(value: u16) -> bool {
+    return value < ESCAPE as u16 || value >= FIRST_ESCAPED;
+}
+
+/// The number of bytes an opcode takes up once encoded.
+pub const fn encoded_len(value: u16) -> usize {
+    if value >= FIRST_ESCAPED {
+        return 2;
+    } else {
+        return 1;
+    }
+}
+
+/// Encodes a valid opcode.
+pub fn encode(value: u16) -> Vec<u8> {
+    if value >= FIRST_ESCAPED {
+        return vec![ESCAPE, value as u8];
+    } else {
+        return vec![value as u8];
+    }
+}
+
+/// Decodes the opcode at the start of a byte slice, returning it along with the number of bytes it
+/// took up.
+pub fn decode(bytes: &[u8]) -> Option<(u16, usize)> {
+    return match *bytes.first()? {
+        ESCAPE => Some((FIRST_ESCAPED | *bytes.get(1)? as u16, 2)),
+        byte => Some((byte as u16, 1)),
+    };
+}
+
+impl fmt::Display for UnknownOpcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "unknown opcode");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::Opcode;
+    use alloc::string::ToString;
+
+    #[test]
+    pub fn test_encoding() {
+        assert_eq!(encode(0x37), vec![0x37]);
+        assert_eq!(encode(0xff12), vec![0xff, 0x12]);
+        assert_eq!(decode(&[0x37, 0xff]), Some((0x37, 1)));
+        assert_eq!(decode(&[0xff, 0x12]), Some((0xff12, 2)));
+        assert_eq!(decode(&[0xff]), None);
+        assert!(!is_valid(0xff));
+        assert!(!is_valid(0x0100));
+    }
+
+    #[test]
+    pub fn test_opcode() {
+        assert_eq!("csel".parse(), Ok(Opcode::Csel));
+        assert_eq!("csell".parse::<Opcode>(), Err(UnknownOpcode));
+        assert_eq!(Opcode::try_from(0x37u8), Ok(Opcode::Jmp));
+        assert_eq!(Opcode::Copyi.register_count(), 2);
+        assert_eq!(Opcode::Copyi.immediate_count(), 3);
+        assert_eq!(Opcode::Copyi.to_string(), "copyi");
+        assert!(Opcode::iter()
+            .zip(Opcode::iter().skip(1))
+            .all(|(a, b)| a < b));
+        assert!(Opcode::iter().all(|opcode| opcode.info().opcode() == opcode.value()));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::encoding::InstructionEncoding;
+use crate::instruction::Instruction;
+use crate::instruction_arguments::Operand;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A program decoded ahead of time into a dense array of instructions.
+///
+/// Instructions are addressed by their index in the array. The offset of every instruction is kept
+/// so addresses can be converted to indices, and the targets of branches and the handlers of `try`,
+/// given by either a code address or a displacement, are resolved to indices while decoding so
+/// interpreters never look them up while running.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+    offsets: Vec<u64>,
+    targets: Vec<Option<usize>>,
+}
+
+/// The error returned when the bytes of a program do not hold a valid instruction.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DecodeError {
+    offset: u64,
+}
+
+impl Program {
+    /// Decodes every instruction of a program in the fixed encoding, the first instruction is at
+    /// offset 0.
+    pub fn predecode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        return Self::predecode_with(bytes, InstructionEncoding::Fixed);
+    }
+
+    /// Decodes every instruction of a program in an encoding, the first instruction is at offset 0.
+    pub fn predecode_with(
+        bytes: &[u8],
+        encoding: InstructionEncoding,
+    ) -> Result<Self, DecodeError> {
+        let mut instructions = Vec::new();
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let (instruction, len) = encoding
+                .decode(&bytes[offset..])
+                .ok_or(DecodeError::new(offset as u64))?;
+
+            instructions.push(instruction);
+            offsets.push(offset as u64);
+            offset += len;
+        }
+
+        let mut program = Self {
+            instructions,
+            offsets,
+            targets: Vec::new(),
+        };
+
+        program.targets = program
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| {
+                let end = match program.offsets.get(index + 1) {
+                    Some(end) => *end,
+                    None => bytes.len() as u64,
+                };
+
+                return instruction.operands().find_map(|operand| match operand {
+                    Operand::Address(address) if address.is_code() => {
+                        program.index_of(address.into())
+                    }
+                    Operand::Displacement(displacement) => {
+                        program.index_of(displacement.target(end))
+                    }
+                    _ => None,
+                });
+            })
+            .collect();
+
+        return Ok(program);
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        return &self.instructions;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.instructions.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.instructions.is_empty();
+    }
+
+    pub fn get(&self, index: usize) -> Option<Instruction> {
+        return self.instructions.get(index).copied();
+    }
+
+    /// The offset of the instruction at an index.
+    pub fn offset_of(&self, index: usize) -> Option<u64> {
+        return self.offsets.get(index).copied();
+    }
+
+    /// The index of the instruction starting at an offset, or None if no instruction starts there.
+    pub fn index_of(&self, offset: u64) -> Option<usize> {
+        return self.offsets.binary_search(&offset).ok();
+    }
+
+    /// The index of the instruction the code address or displacement of the instruction at an index
+    /// refers to, e.g. where a branch jumps to or the handler of a `try`. None if the instruction
+    /// has neither, or its target is not the start of an instruction.
+    pub fn target(&self, index: usize) -> Option<usize> {
+        return self.targets.get(index).copied().flatten();
+    }
+}
+
+impl DecodeError {
+    pub const fn new(offset: u64) -> Self {
+        return Self { offset };
+    }
+
+    /// The offset of the instruction that could not be decoded.
+    pub const fn offset(&self) -> u64 {
+        return self.offset;
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "invalid instruction at offset {}", self.offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction_arguments::{Address, Displacement, Immediate, Register};
+
+    #[test]
+    pub fn test_predecode() {
+        let instructions = [
+            Instruction::Ldi(Immediate::from(3i64), Register::R0),
+            Instruction::Nop,
+            Instruction::Jmp(Address::from(9u64)),
+            Instruction::Jmp(Address::from(10u64)),
+            Instruction::Jmp8(Displacement::new(-21)),
+            Instruction::Ret,
+            Instruction::Try(Address::from(10u64)),
+        ];
+        let mut bytes = Vec::new();
+
+        for instruction in instructions {
+            bytes.extend(Into::<Vec<u8>>::into(instruction));
+        }
+
+        let program = Program::predecode(&bytes).unwrap();
+
+        assert_eq!(program.instructions(), &instructions);
+        assert_eq!(program.offset_of(2), Some(11));
+        assert_eq!(program.index_of(10), Some(1));
+        assert_eq!(program.index_of(11), Some(2));
+        assert_eq!(program.index_of(12), None);
+        assert_eq!(program.target(2), None);
+        assert_eq!(program.target(3), Some(1));
+        assert_eq!(program.target(4), Some(2));
+        assert_eq!(program.target(0), None);
+        assert_eq!(program.target(6), Some(1));
+
+        bytes.push(0xfe);
+
+        assert_eq!(Program::predecode(&bytes), Err(DecodeError::new(42)));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::program::Program;
+use alloc::string::String;
+use core::fmt;
+
+/// What a section of a vxl file holds.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[repr(u8)]
+pub enum SectionKind {
+    /// Instructions, in the encoding given by the `Section::COMPACT` flag.
+    Code = 0,
+    /// Data the program reads but never writes.
+    ReadOnlyData = 1,
+    /// Data the program starts with and may write.
+    Data = 2,
+    /// Zeroed memory, only its length is stored in the file.
+    Bss = 3,
+    /// The symbol table.
+    Symbols = 4,
+    /// Debug information, ignored when running the program.
+    Debug = 5,
+    /// The relocations of an object, see `object::Relocation`.
+    Relocations = 6,
+}
+
+/// An entry of the section table of a vxl file.
+///
+/// # Representation when in binary
+/// Byte Offset - Value
+///
+/// 0x0 - Name (UTF-8, padded with zeroes to 16 bytes)
+///
+/// 0x10 - Kind, see `SectionKind`
+///
+/// 0x11 - Flags (From LSB to MSB 0 = Readable, 1 = Writable, 2 = Executable, 3 = Compact encoding)
+///
+/// 0x12 - Offset of the contents from the start of the file contents (Little endian)
+///
+/// 0x1a - Length of the contents in bytes (Little endian)
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Section {
+    name: String,
+    kind: SectionKind,
+    flags: u8,
+    offset: u64,
+    length: u64,
+}
+
+/// Implemented by virtual machines to map the sections of a vxl file into their memory, see
+/// `VXLFile::load`.
+///
+/// Sections are loaded in the order of the section table. Code sections are decoded in their
+/// encoding and given to `load_code`, branch targets within a section being resolved by the
+/// `Program`. Read only data, data and bss sections are given to `map_memory` along with their
+/// contents, which are empty for bss. The machine allocates `section.length()` bytes, copies the
+/// contents to their start and zeroes the rest, and must reject writes to sections without the
+/// `Section::WRITABLE` flag. Symbol, debug and relocation sections are not part of the running
+/// program and are skipped.
+pub trait SectionLoader {
+    type Error;
+
+    /// Loads the decoded instructions of a code section.
+    fn load_code(&mut self, section: &Section, program: Program) -> Result<(), Self::Error>;
+
+    /// Maps a read only data, data or bss section into memory.
+    fn map_memory(&mut self, section: &Section, contents: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl SectionKind {
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        return match value {
+            0 => Some(SectionKind::Code),
+            1 => Some(SectionKind::ReadOnlyData),
+            2 => Some(SectionKind::Data),
+            3 => Some(SectionKind::Bss),
+            4 => Some(SectionKind::Symbols),
+            5 => Some(SectionKind::Debug),
+            6 => Some(SectionKind::Relocations),
+            _ => None,
+        };
+    }
+
+    /// The flags given to sections of this kind unless others are requested.
+    pub const fn default_flags(self) -> u8 {
+        return match self {
+            SectionKind::Code => Section::READABLE | Section::EXECUTABLE,
+            SectionKind::ReadOnlyData => Section::READABLE,
+            SectionKind::Data | SectionKind::Bss => Section::READABLE | Section::WRITABLE,
+            SectionKind::Symbols | SectionKind::Debug | SectionKind::Relocations => 0,
+        };
+    }
+
+    /// True if sections of this kind are mapped into the memory of the virtual machine.
+    pub const fn is_memory(self) -> bool {
+        return matches!(
+            self,
+            SectionKind::ReadOnlyData | SectionKind::Data | SectionKind::Bss
+        );
+    }
+}
+
+impl Section {
+    /// The size in bytes of an entry of the section table.
+    pub const ENTRY_SIZE: usize = 34;
+    /// The largest number of bytes in the name of a section.
+    pub const MAX_NAME_LEN: usize = 16;
+    /// The flag set if the program may read the section.
+    pub const READABLE: u8 = 0b0000_0001;
+    /// The flag set if the program may write the section.
+    pub const WRITABLE: u8 = 0b0000_0010;
+    /// The flag set if the section holds instructions that may be executed.
+    pub const EXECUTABLE: u8 = 0b0000_0100;
+    /// The flag set if the instructions of a code section are in the compact encoding.
+    pub const COMPACT: u8 = 0b0000_1000;
+
+    /// Creates a new entry, returns None if the name is longer than `MAX_NAME_LEN` bytes.
+    pub fn new(name: &str, kind: SectionKind, flags: u8, offset: u64, length: u64) -> Option<Self> {
+        if name.len() > Self::MAX_NAME_LEN {
+            return None;
+        }
+
+        return Some(Self {
+            name: String::from(name),
+            kind,
+            flags,
+            offset,
+            length,
+        });
+    }
+
+    /// Reads an entry from the start of a byte slice, returns None if the slice is too short or
+    /// the entry is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..Self::ENTRY_SIZE)?;
+        let name = &bytes[..Self::MAX_NAME_LEN];
+        let name_len = name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(name.len());
+
+        return Some(Self {
+            name: String::from(core::str::from_utf8(&name[..name_len]).ok()?),
+            kind: SectionKind::from_u8(bytes[16])?,
+            flags: bytes[17],
+            offset: u64::from_le_bytes(bytes[18..26].try_into().ok()?),
+            length: u64::from_le_bytes(bytes[26..34].try_into().ok()?),
+        });
+    }
+
+    pub fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    pub fn kind(&self) -> SectionKind {
+        return self.kind;
+    }
+
+    pub fn flags(&self) -> u8 {
+        return self.flags;
+    }
+
+    /// The offset of the contents of the section from the start of the file contents.
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+
+    /// The length of the section in bytes. For bss sections this is the size of the zeroed memory,
+    /// which takes up no bytes in the file.
+    pub fn length(&self) -> u64 {
+        return self.length;
+    }
+
+    /// The number of bytes the contents of the section take up in the file.
+    pub fn file_length(&self) -> u64 {
+        return match self.kind {
+            SectionKind::Bss => 0,
+            _ => self.length,
+        };
+    }
+
+    pub fn is_readable(&self) -> bool {
+        return self.flags & Self::READABLE != 0;
+    }
+
+    pub fn is_writable(&self) -> bool {
+        return self.flags & Self::WRITABLE != 0;
+    }
+
+    pub fn is_executable(&self) -> bool {
+        return self.flags & Self::EXECUTABLE != 0;
+    }
+
+    pub fn is_compact(&self) -> bool {
+        return self.flags & Self::COMPACT != 0;
+    }
+}
+
+impl Into<[u8; Section::ENTRY_SIZE]> for &Section {
+    fn into(self) -> [u8; Section::ENTRY_SIZE] {
+        let mut bytes = [0; Section::ENTRY_SIZE];
+
+        bytes[..self.name.len()].copy_from_slice(self.name.as_bytes());
+        bytes[16] = self.kind as u8;
+        bytes[17] = self.flags;
+        bytes[18..26].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[26..34].copy_from_slice(&self.length.to_le_bytes());
+
+        return bytes;
+    }
+}
+
+impl fmt::Display for SectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SectionKind::Code => "code",
+            SectionKind::ReadOnlyData => "rodata",
+            SectionKind::Data => "data",
+            SectionKind::Bss => "bss",
+            SectionKind::Symbols => "symbols",
+            SectionKind::Debug => "debug",
+            SectionKind::Relocations => "relocations",
+        };
+
+        return write!(f, "{}", name);
+    }
+}
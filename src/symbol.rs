@@ -0,0 +1,297 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// What a symbol names.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum SymbolKind {
+    /// A function in a code section, its size covers every instruction of the function.
+    Function = 0,
+    /// An instruction in a code section, e.g. the target of a branch.
+    Label = 1,
+    /// A value in a data section.
+    Object = 2,
+}
+
+/// Whether a symbol can be referred to from other objects.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum SymbolBinding {
+    /// Only visible within the object defining it.
+    Local = 0,
+    /// Exported to every object linked with the object defining it.
+    Global = 1,
+}
+
+/// A name given to an offset within a section.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Symbol {
+    name: String,
+    kind: SymbolKind,
+    binding: SymbolBinding,
+    section: u16,
+    offset: u64,
+    size: u64,
+}
+
+/// The contents of a symbol section, the symbols are kept ordered by their offset.
+///
+/// # Representation when in binary
+/// Byte Offset - Value
+///
+/// 0x0 - Number of symbols (Little endian, 4 bytes)
+///
+/// Followed by each symbol:
+///
+/// 0x0 - Kind, see `SymbolKind`
+///
+/// 0x1 - Binding, see `SymbolBinding`
+///
+/// 0x2 - Index of the section in the section table (Little endian, 2 bytes)
+///
+/// 0x4 - Offset within the section (Little endian)
+///
+/// 0xc - Size in bytes (Little endian)
+///
+/// 0x14 - Length of the name in bytes
+///
+/// 0x15 - Name (UTF-8)
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolKind {
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        return match value {
+            0 => Some(SymbolKind::Function),
+            1 => Some(SymbolKind::Label),
+            2 => Some(SymbolKind::Object),
+            _ => None,
+        };
+    }
+
+    /// True if symbols of this kind name an offset in a code section.
+    pub const fn is_code(self) -> bool {
+        return matches!(self, SymbolKind::Function | SymbolKind::Label);
+    }
+}
+
+impl SymbolBinding {
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        return match value {
+            0 => Some(SymbolBinding::Local),
+            1 => Some(SymbolBinding::Global),
+            _ => None,
+        };
+    }
+}
+
+impl Symbol {
+    /// The largest number of bytes in the name of a symbol.
+    pub const MAX_NAME_LEN: usize = u8::MAX as usize;
+
+    /// Creates a new local symbol, returns None if the name is longer than `MAX_NAME_LEN` bytes.
+    pub fn new(name: &str, kind: SymbolKind, section: u16, offset: u64, size: u64) -> Option<Self> {
+        if name.len() > Self::MAX_NAME_LEN {
+            return None;
+        }
+
+        return Some(Self {
+            name: String::from(name),
+            kind,
+            binding: SymbolBinding::Local,
+            section,
+            offset,
+            size,
+        });
+    }
+
+    pub fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    pub fn kind(&self) -> SymbolKind {
+        return self.kind;
+    }
+
+    pub fn binding(&self) -> SymbolBinding {
+        return self.binding;
+    }
+
+    pub fn with_binding(mut self, binding: SymbolBinding) -> Self {
+        self.binding = binding;
+
+        return self;
+    }
+
+    /// True if other objects can refer to the symbol.
+    pub fn is_global(&self) -> bool {
+        return self.binding == SymbolBinding::Global;
+    }
+
+    /// The index of the section the symbol is in.
+    pub fn section(&self) -> u16 {
+        return self.section;
+    }
+
+    /// The offset of the symbol from the start of its section.
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+
+    /// The number of bytes the symbol covers, 0 for labels.
+    pub fn size(&self) -> u64 {
+        return self.size;
+    }
+
+    /// True if the symbol starts at an offset or covers it.
+    pub fn contains(&self, offset: u64) -> bool {
+        return offset == self.offset || (offset > self.offset && offset - self.offset < self.size);
+    }
+}
+
+impl SymbolTable {
+    pub fn new(mut symbols: Vec<Symbol>) -> Self {
+        symbols.sort_by_key(|symbol| (symbol.offset, symbol.kind));
+
+        return Self { symbols };
+    }
+
+    /// Reads the contents of a symbol section, returns None if they are truncated or invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let count = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+        let mut symbols = Vec::new();
+        let mut offset = 4;
+
+        for _ in 0..count {
+            let entry = bytes.get(offset..offset + 21)?;
+            let name_len = entry[20] as usize;
+            let name = bytes.get(offset + 21..offset + 21 + name_len)?;
+
+            symbols.push(Symbol {
+                name: String::from(core::str::from_utf8(name).ok()?),
+                kind: SymbolKind::from_u8(entry[0])?,
+                binding: SymbolBinding::from_u8(entry[1])?,
+                section: u16::from_le_bytes(entry[2..4].try_into().ok()?),
+                offset: u64::from_le_bytes(entry[4..12].try_into().ok()?),
+                size: u64::from_le_bytes(entry[12..20].try_into().ok()?),
+            });
+            offset += 21 + name_len;
+        }
+
+        return Some(Self::new(symbols));
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        return &self.symbols;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.symbols.is_empty();
+    }
+
+    /// The function or label at an offset in the code, used to name an offset in backtraces and
+    /// disassembly. A label starting at the offset is preferred over the function covering it.
+    pub fn symbol_at(&self, offset: u64) -> Option<&Symbol> {
+        return self
+            .symbols
+            .iter()
+            .rev()
+            .filter(|symbol| symbol.kind.is_code() && symbol.offset <= offset)
+            .find(|symbol| symbol.contains(offset));
+    }
+
+    /// The function covering an offset in the code.
+    pub fn function_at(&self, offset: u64) -> Option<&Symbol> {
+        return self
+            .symbols
+            .iter()
+            .rev()
+            .find(|symbol| symbol.kind == SymbolKind::Function && symbol.contains(offset));
+    }
+
+    /// The object in a data section covering an offset.
+    pub fn object_at(&self, section: u16, offset: u64) -> Option<&Symbol> {
+        return self.symbols.iter().rev().find(|symbol| {
+            symbol.kind == SymbolKind::Object
+                && symbol.section == section
+                && symbol.contains(offset)
+        });
+    }
+
+    pub fn symbol_by_name(&self, name: &str) -> Option<&Symbol> {
+        return self.symbols.iter().find(|symbol| symbol.name == name);
+    }
+}
+
+impl Into<Vec<u8>> for &SymbolTable {
+    fn into(self) -> Vec<u8> {
+        let mut bytes = (self.symbols.len() as u32).to_le_bytes().to_vec();
+
+        for symbol in &self.symbols {
+            bytes.push(symbol.kind as u8);
+            bytes.push(symbol.binding as u8);
+            bytes.extend_from_slice(&symbol.section.to_le_bytes());
+            bytes.extend_from_slice(&symbol.offset.to_le_bytes());
+            bytes.extend_from_slice(&symbol.size.to_le_bytes());
+            bytes.push(symbol.name.len() as u8);
+            bytes.extend_from_slice(symbol.name.as_bytes());
+        }
+
+        return bytes;
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_symbol_lookup() {
+        let table = SymbolTable::new(
+            [
+                ("loop", SymbolKind::Label, 0, 14, 0),
+                ("main", SymbolKind::Function, 0, 0, 30),
+                ("square", SymbolKind::Function, 0, 30, 12),
+                ("table", SymbolKind::Object, 1, 0, 64),
+            ]
+            .into_iter()
+            .filter_map(|(name, kind, section, offset, size)| {
+                Symbol::new(name, kind, section, offset, size)
+            })
+            .map(|symbol| match symbol.kind() {
+                SymbolKind::Function => symbol.with_binding(SymbolBinding::Global),
+                _ => symbol,
+            })
+            .collect(),
+        );
+        fn name(symbol: Option<&Symbol>) -> Option<&str> {
+            return symbol.map(Symbol::name);
+        }
+
+        assert_eq!(name(table.symbol_at(0)), Some("main"));
+        assert_eq!(name(table.symbol_at(14)), Some("loop"));
+        assert_eq!(name(table.symbol_at(20)), Some("main"));
+        assert_eq!(name(table.symbol_at(41)), Some("square"));
+        assert_eq!(name(table.symbol_at(42)), None);
+        assert_eq!(name(table.function_at(14)), Some("main"));
+        assert_eq!(name(table.object_at(1, 63)), Some("table"));
+        assert_eq!(name(table.object_at(0, 0)), None);
+        assert_eq!(table.symbol_by_name("square").unwrap().offset(), 30);
+        assert!(table.symbol_by_name("square").unwrap().is_global());
+        assert!(!table.symbol_by_name("loop").unwrap().is_global());
+
+        let bytes: Vec<u8> = (&table).into();
+
+        assert_eq!(SymbolTable::from_bytes(&bytes), Some(table));
+        assert_eq!(SymbolTable::from_bytes(&bytes[..bytes.len() - 1]), None);
+    }
+}
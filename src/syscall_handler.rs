@@ -1,12 +1,10 @@
-use crate::execute_instruction::ExecuteInstruction;
-
 macro_rules! define_default_system_call {
     ($name:ident) => {
         fn $name(&mut self, machine: &mut M) -> Option<u64>;
     };
 }
 
-pub trait SyscallHandler<M: ExecuteInstruction> {
+pub trait SyscallHandler<M> {
     fn execute_call(&mut self, call: u64, machine: &mut M) -> Option<u64> {
         return match call {
             0 => self.exit(machine),
@@ -1,4 +1,12 @@
+use crate::debug_info::LineTable;
+use crate::encoding::InstructionEncoding;
+use crate::extension::Extension;
+use crate::program::{DecodeError, Program};
+use crate::section::{Section, SectionKind, SectionLoader};
+use crate::symbol::SymbolTable;
+use alloc::vec;
 use alloc::vec::Vec;
+use core::fmt;
 
 /// Defines what the header of a vxl file should contain.
 ///
@@ -7,13 +15,15 @@ use alloc::vec::Vec;
 ///
 /// 0x0 - Magic bytes (0x65, 0x58, 0x56, 0x4c)
 ///
-/// 0x4 - Executable version
+/// 0x4 - Executable version, which also selects the layout of the contents (0 = program in the fixed
+/// encoding, 1 = program in the compact encoding, 2 = section table), see `InstructionEncoding`
 ///
 /// 0x5 - File size in bytes excluding header (Little endian)
 ///
 /// 0xd - Starting instruction offset (Little endian)
 ///
-/// 0x15 - Flags (From LSB to MSB 0 = Hash algorithm (1 = SHA3-224, 0 = SHA2-224))
+/// 0x15 - Flags (From LSB to MSB 0 = Hash algorithm (1 = SHA3-224, 0 = SHA2-224), 1-3 = Required
+/// extensions, see `Extension::header_flag`)
 ///
 /// 0x16 - Checksum (SHA3 or SHA2 hash of the expected file. (28 bytes))
 ///
@@ -27,22 +37,66 @@ pub struct VXLHeader {
     checksum: [u8; Self::HEADER_CHECKSUM_SIZE],
 }
 
-/// Represents an executable vxl file. A vxl file is simply the header followed by the program bytes.
+/// Represents an executable vxl file. A vxl file is the header followed by its contents, which are
+/// either the program bytes or, from version 2, a section table and the contents of its sections.
+///
+/// # Representation of the contents of version 2
+/// Byte Offset - Value
+///
+/// 0x0 - Number of sections (Little endian, 4 bytes)
+///
+/// 0x4 - The section table, an entry of `Section::ENTRY_SIZE` bytes per section, see `Section`
+///
+/// The contents of the sections follow the table. The program starts at the starting offset of the
+/// first code section.
 #[derive(Clone, PartialEq, Debug)]
 pub struct VXLFile {
     header: VXLHeader,
     contents: Vec<u8>,
 }
 
+/// The error returned when the program of a vxl file cannot be loaded.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LoadError {
+    /// The version of the file is not one of `VXLHeader::SUPPORTED_VERSIONS`.
+    UnsupportedVersion(u8),
+    /// The program does not hold valid instructions.
+    Decode(DecodeError),
+    /// The section table is truncated, holds an invalid entry or refers to bytes past the end of
+    /// the contents.
+    InvalidSections,
+    /// The file has no code section.
+    MissingCode,
+    /// The symbol section is truncated or holds an invalid symbol.
+    InvalidSymbols,
+    /// The line table is truncated or refers to a file it does not name.
+    InvalidLineTable,
+}
+
+/// Builds a vxl file with a section table, sections are laid out in the order they are added. The
+/// header records the extensions required by the instructions of every code section.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct VXLBuilder {
+    sections: Vec<(Section, Vec<u8>)>,
+    starting_offset: u64,
+    flags: u8,
+    extensions: u8,
+    checksum: [u8; VXLHeader::HEADER_CHECKSUM_SIZE],
+}
+
 impl VXLHeader {
     /// The size in bytes of the checksum
     pub const HEADER_CHECKSUM_SIZE: usize = 28;
     /// The list of supported vxl versions.
-    pub const SUPPORTED_VERSIONS: [u8; 1] = [0x0];
+    pub const SUPPORTED_VERSIONS: [u8; 3] = [0x0, 0x1, 0x2];
+    /// The first version whose contents start with a section table.
+    pub const SECTIONED_VERSION: u8 = 0x2;
     /// The full size fo the header.
     pub const HEADER_SIZE: usize = 50;
     /// The mask when the bitwise 'and' operation is applied to the flag gets the value of the checksum algorithm.
     pub const CHECKSUM_MASK: u8 = 0b0000_0001;
+    /// The mask when the bitwise 'and' operation is applied to the flag gets the required extensions.
+    pub const EXTENSIONS_MASK: u8 = 0b0000_1110;
     /// The magic bytes: 0x65, 0x58, 0x56, 0x4c. These are used to verify that the header
     /// is indeed a vxl file.
     pub const MAGIC: [u8; 4] = [0x65, 0x58, 0x56, 0x4c];
@@ -71,6 +125,17 @@ impl VXLHeader {
         return self.version;
     }
 
+    /// The encoding of the program, or None if the version is not supported or the file has a
+    /// section table, in which case each code section records its own encoding.
+    pub fn instruction_encoding(&self) -> Option<InstructionEncoding> {
+        return InstructionEncoding::for_version(self.version);
+    }
+
+    /// True if the contents of the file start with a section table.
+    pub fn has_sections(&self) -> bool {
+        return self.version >= Self::SECTIONED_VERSION;
+    }
+
     /// The size of the file that this is the header of.
     pub fn size(&self) -> u64 {
         return self.file_size;
@@ -100,6 +165,18 @@ impl VXLHeader {
     pub fn checksum_sha2(&self) -> bool {
         return (self.flags & Self::CHECKSUM_MASK) == 0;
     }
+
+    /// True if the program requires an extension.
+    pub fn requires_extension(&self, extension: Extension) -> bool {
+        return (self.flags & extension.header_flag()) != 0;
+    }
+
+    /// The extensions the program requires that were not enabled in this build.
+    pub fn missing_extensions(&self) -> impl Iterator<Item = Extension> + '_ {
+        return Extension::ALL
+            .into_iter()
+            .filter(|extension| self.requires_extension(*extension) && !extension.is_enabled());
+    }
 }
 
 impl VXLFile {
@@ -117,6 +194,265 @@ impl VXLFile {
     pub fn contents(&self) -> &Vec<u8> {
         return &self.contents;
     }
+
+    /// The sections of the file. Files without a section table have a single code section named
+    /// `code` holding the whole program.
+    pub fn sections(&self) -> Result<Vec<Section>, LoadError> {
+        if !VXLHeader::SUPPORTED_VERSIONS.contains(&self.header.version) {
+            return Err(LoadError::UnsupportedVersion(self.header.version));
+        }
+
+        if let Some(encoding) = self.header.instruction_encoding() {
+            let mut flags = SectionKind::Code.default_flags();
+
+            if encoding == InstructionEncoding::Compact {
+                flags |= Section::COMPACT;
+            }
+
+            return Ok(vec![VXLBuilder::entry(
+                "code",
+                SectionKind::Code,
+                flags,
+                self.contents.len() as u64,
+            )]);
+        }
+
+        let count = self
+            .contents
+            .get(..4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(LoadError::InvalidSections)? as usize;
+        let mut sections = Vec::new();
+
+        for index in 0..count {
+            let section = self
+                .contents
+                .get(4 + index * Section::ENTRY_SIZE..)
+                .and_then(Section::from_bytes)
+                .ok_or(LoadError::InvalidSections)?;
+            let end = section.offset().checked_add(section.file_length());
+
+            if end.is_none_or(|end| end > self.contents.len() as u64) {
+                return Err(LoadError::InvalidSections);
+            }
+
+            sections.push(section);
+        }
+
+        return Ok(sections);
+    }
+
+    /// The bytes of a section of this file, empty for bss sections. Sections reaching past the end
+    /// of the file are cut short.
+    pub fn section_data(&self, section: &Section) -> &[u8] {
+        let len = self.contents.len();
+        let start = usize::try_from(section.offset()).map_or(len, |offset| offset.min(len));
+        let end = usize::try_from(section.file_length())
+            .map_or(len, |length| start.saturating_add(length).min(len));
+
+        return &self.contents[start..end];
+    }
+
+    /// The symbols of the file, read from its first symbol section. Empty if the file has none.
+    pub fn symbols(&self) -> Result<SymbolTable, LoadError> {
+        return match self
+            .sections()?
+            .iter()
+            .find(|section| section.kind() == SectionKind::Symbols)
+        {
+            Some(section) => {
+                SymbolTable::from_bytes(self.section_data(section)).ok_or(LoadError::InvalidSymbols)
+            }
+            None => Ok(SymbolTable::default()),
+        };
+    }
+
+    /// The line table of the file, read from the debug section named `LineTable::SECTION_NAME`.
+    /// Empty if the file has none.
+    pub fn line_table(&self) -> Result<LineTable, LoadError> {
+        return match self.sections()?.iter().find(|section| {
+            section.kind() == SectionKind::Debug && section.name() == LineTable::SECTION_NAME
+        }) {
+            Some(section) => {
+                LineTable::from_bytes(self.section_data(section)).ok_or(LoadError::InvalidLineTable)
+            }
+            None => Ok(LineTable::default()),
+        };
+    }
+
+    /// Decodes the program, the instructions of the first code section.
+    pub fn program(&self) -> Result<Program, LoadError> {
+        let sections = self.sections()?;
+        let code = sections
+            .iter()
+            .find(|section| section.kind() == SectionKind::Code)
+            .ok_or(LoadError::MissingCode)?;
+
+        return Ok(decode_section(self.section_data(code), code)?);
+    }
+
+    /// Loads every section of the file into a virtual machine, following the contract described by
+    /// `SectionLoader`.
+    pub fn load<L: SectionLoader>(&self, loader: &mut L) -> Result<(), L::Error>
+    where
+        L::Error: From<LoadError>,
+    {
+        for section in self.sections()? {
+            let contents = self.section_data(&section);
+
+            if section.kind() == SectionKind::Code {
+                let program = decode_section(contents, &section).map_err(LoadError::from)?;
+
+                loader.load_code(&section, program)?;
+            } else if section.kind().is_memory() {
+                loader.map_memory(&section, contents)?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/// Decodes the instructions of a code section in its encoding.
+fn decode_section(contents: &[u8], section: &Section) -> Result<Program, DecodeError> {
+    let encoding = match section.is_compact() {
+        true => InstructionEncoding::Compact,
+        false => InstructionEncoding::Fixed,
+    };
+
+    return Program::predecode_with(contents, encoding);
+}
+
+impl VXLBuilder {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Sets the offset within the first code section the program starts at.
+    pub fn starting_offset(mut self, starting_offset: u64) -> Self {
+        self.starting_offset = starting_offset;
+
+        return self;
+    }
+
+    /// Sets the flags of the header, the flags of the extensions the code requires are added to
+    /// them when building.
+    pub fn flags(mut self, flags: u8) -> Self {
+        self.flags = flags;
+
+        return self;
+    }
+
+    /// Sets the checksum of the header.
+    pub fn checksum(mut self, checksum: [u8; VXLHeader::HEADER_CHECKSUM_SIZE]) -> Self {
+        self.checksum = checksum;
+
+        return self;
+    }
+
+    /// Adds a code section holding instructions in an encoding. Code that cannot be decoded
+    /// requires no extensions.
+    pub fn code(mut self, name: &str, bytes: Vec<u8>, encoding: InstructionEncoding) -> Self {
+        let mut flags = SectionKind::Code.default_flags();
+
+        if let Ok(program) = Program::predecode_with(&bytes, encoding) {
+            self.extensions |= Extension::required_flags(program.instructions());
+        }
+
+        if encoding == InstructionEncoding::Compact {
+            flags |= Section::COMPACT;
+        }
+
+        return self.section_with_flags(name, SectionKind::Code, flags, bytes);
+    }
+
+    /// Adds a symbol section holding a symbol table.
+    pub fn symbols(self, name: &str, symbols: &SymbolTable) -> Self {
+        return self.section(name, SectionKind::Symbols, symbols.into());
+    }
+
+    /// Adds a debug section named `LineTable::SECTION_NAME` holding a line table.
+    pub fn line_table(self, lines: &LineTable) -> Self {
+        return self.section(LineTable::SECTION_NAME, SectionKind::Debug, lines.into());
+    }
+
+    /// Adds a bss section of a length.
+    pub fn bss(mut self, name: &str, length: u64) -> Self {
+        let flags = SectionKind::Bss.default_flags();
+
+        self.sections.push((
+            Self::entry(name, SectionKind::Bss, flags, length),
+            Vec::new(),
+        ));
+
+        return self;
+    }
+
+    /// Adds a section with the default flags of its kind.
+    pub fn section(self, name: &str, kind: SectionKind, contents: Vec<u8>) -> Self {
+        return self.section_with_flags(name, kind, kind.default_flags(), contents);
+    }
+
+    /// Adds a section, only the length of the contents is kept for bss sections.
+    ///
+    /// # Panics
+    /// If the name is longer than `Section::MAX_NAME_LEN` bytes.
+    pub fn section_with_flags(
+        mut self,
+        name: &str,
+        kind: SectionKind,
+        flags: u8,
+        contents: Vec<u8>,
+    ) -> Self {
+        let entry = Self::entry(name, kind, flags, contents.len() as u64);
+
+        self.sections.push(match kind {
+            SectionKind::Bss => (entry, Vec::new()),
+            _ => (entry, contents),
+        });
+
+        return self;
+    }
+
+    fn entry(name: &str, kind: SectionKind, flags: u8, length: u64) -> Section {
+        return Section::new(name, kind, flags, 0, length)
+            .expect("section names are at most Section::MAX_NAME_LEN bytes");
+    }
+
+    /// Lays out the sections after the section table and builds the file.
+    pub fn build(self) -> VXLFile {
+        let mut contents = (self.sections.len() as u32).to_le_bytes().to_vec();
+        let mut offset = (contents.len() + self.sections.len() * Section::ENTRY_SIZE) as u64;
+        let mut data = Vec::new();
+
+        for (section, bytes) in &self.sections {
+            let section = Section::new(
+                section.name(),
+                section.kind(),
+                section.flags(),
+                offset,
+                section.length(),
+            )
+            .unwrap();
+
+            contents.extend_from_slice(&Into::<[u8; Section::ENTRY_SIZE]>::into(&section));
+            data.extend_from_slice(bytes);
+            offset += bytes.len() as u64;
+        }
+
+        contents.append(&mut data);
+
+        let header = VXLHeader::new(
+            VXLHeader::SECTIONED_VERSION,
+            contents.len() as u64,
+            self.starting_offset,
+            self.flags | self.extensions,
+            self.checksum,
+        );
+
+        return VXLFile::new(header, contents);
+    }
 }
 
 impl Into<Vec<u8>> for VXLHeader {
@@ -144,3 +480,136 @@ impl Into<Vec<u8>> for VXLFile {
         return bytes;
     }
 }
+
+impl From<DecodeError> for LoadError {
+    fn from(e: DecodeError) -> Self {
+        return LoadError::Decode(e);
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            LoadError::UnsupportedVersion(version) => {
+                write!(f, "unsupported vxl version {}", version)
+            }
+            LoadError::Decode(e) => write!(f, "{}", e),
+            LoadError::InvalidSections => write!(f, "invalid section table"),
+            LoadError::MissingCode => write!(f, "no code section"),
+            LoadError::InvalidSymbols => write!(f, "invalid symbol table"),
+            LoadError::InvalidLineTable => write!(f, "invalid line table"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::Instruction;
+    use crate::instruction_arguments::{Immediate, Register};
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    /// Records what was loaded.
+    #[derive(Default)]
+    struct Loader {
+        code: Vec<(String, Program)>,
+        memory: Vec<(String, u64, Vec<u8>)>,
+    }
+
+    impl SectionLoader for Loader {
+        type Error = LoadError;
+
+        fn load_code(&mut self, section: &Section, program: Program) -> Result<(), LoadError> {
+            self.code.push((section.name().to_string(), program));
+
+            return Ok(());
+        }
+
+        fn map_memory(&mut self, section: &Section, contents: &[u8]) -> Result<(), LoadError> {
+            self.memory.push((
+                section.name().to_string(),
+                section.length(),
+                contents.to_vec(),
+            ));
+
+            return Ok(());
+        }
+    }
+
+    #[test]
+    pub fn test_sections() {
+        let instruction = Instruction::Ldi(Immediate::from(-1i64), Register::R0);
+        let mut code = Vec::new();
+
+        let _ = InstructionEncoding::Compact.encode_to(&instruction, &mut code);
+
+        let file = VXLBuilder::new()
+            .section("rodata", SectionKind::ReadOnlyData, vec![1, 2, 3])
+            .code("main", code.clone(), InstructionEncoding::Compact)
+            .bss("bss", 4096)
+            .section("debug", SectionKind::Debug, vec![9])
+            .build();
+        let sections = file.sections().unwrap();
+
+        assert_eq!(file.header().version(), VXLHeader::SECTIONED_VERSION);
+        assert_eq!(file.header().size(), file.contents().len() as u64);
+        assert_eq!(
+            sections
+                .iter()
+                .map(|section| (section.kind(), section.offset(), section.length()))
+                .collect::<Vec<_>>(),
+            vec![
+                (SectionKind::ReadOnlyData, 140, 3),
+                (SectionKind::Code, 143, code.len() as u64),
+                (SectionKind::Bss, 146, 4096),
+                (SectionKind::Debug, 146, 1),
+            ]
+        );
+        assert!(sections[1].is_compact() && sections[1].is_executable());
+        assert!(sections[2].is_writable() && !sections[0].is_writable());
+        assert_eq!(file.section_data(&sections[3]), &[9]);
+        assert_eq!(
+            file.section_data(&Section::new("big", SectionKind::Data, 0, 146, u64::MAX).unwrap()),
+            &[9]
+        );
+        assert_eq!(file.program().unwrap().instructions(), &[instruction]);
+
+        let mut loader = Loader::default();
+
+        file.load(&mut loader).unwrap();
+
+        assert_eq!(loader.code.len(), 1);
+        assert_eq!(
+            loader.memory,
+            vec![
+                ("rodata".to_string(), 3, vec![1, 2, 3]),
+                ("bss".to_string(), 4096, Vec::new()),
+            ]
+        );
+
+        let mut contents = file.contents().clone();
+
+        contents.truncate(145);
+
+        let file = VXLFile::new(file.header(), contents);
+
+        assert_eq!(file.sections(), Err(LoadError::InvalidSections));
+    }
+
+    #[test]
+    pub fn test_unsectioned_file() {
+        let bytes: Vec<u8> = Instruction::Nop.into();
+        let file = VXLFile::new(VXLHeader::new(0, 1, 0, 0, [0; 28]), bytes);
+        let sections = file.sections().unwrap();
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].kind(), SectionKind::Code);
+        assert!(!sections[0].is_compact());
+        assert_eq!(file.program().unwrap().instructions(), &[Instruction::Nop]);
+        assert_eq!(
+            VXLBuilder::new().build().program(),
+            Err(LoadError::MissingCode)
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use vxl_iset::instruction::Instruction;
-use vxl_iset::instruction_arguments::{Address, Immediate, Register};
+use vxl_iset::instruction_arguments::{Address, Condition, Immediate, Register};
 
 #[test]
 fn copyi_to_assembly() {
@@ -34,3 +34,61 @@ fn jmp_to_assmebly() {
 
     assert_eq!(instruction.into_assembly(), "jmp 0u12");
 }
+
+#[test]
+fn setcc_to_assembly() {
+    let instruction = Instruction::Setcc(Condition::NE, Register::R3);
+
+    assert_eq!(instruction.into_assembly(), "setcc $r3, ne");
+}
+
+#[test]
+fn csel_to_assembly() {
+    let instruction = Instruction::Csel(Condition::LTU, Register::R0, Register::R1, Register::R2);
+
+    assert_eq!(instruction.into_assembly(), "csel $r0, $r1, $r2, ltu");
+}
+
+#[test]
+fn ldfp_to_assembly() {
+    let instruction = Instruction::Ldfp(Immediate::from(-3i64), Register::R0);
+
+    assert_eq!(instruction.into_assembly(), "ldfp $r0, 0i-3");
+}
+
+#[test]
+fn try_to_assembly() {
+    let instruction = Instruction::Try(Address::new(48));
+
+    assert_eq!(instruction.into_assembly(), "try 0u48");
+}
+
+#[test]
+fn brk_to_assembly() {
+    let instruction = Instruction::Brk(Immediate::from(3u64));
+
+    assert_eq!(instruction.into_assembly(), "brk 0u3");
+}
+
+#[test]
+fn immediate_types_survive_decoding() {
+    let instructions = [
+        Instruction::Ldf(Immediate::from(12.22), Register::R0),
+        Instruction::Ldi(Immediate::from(-12i64), Register::R0),
+        Instruction::Slli(Immediate::from(3u64), Register::R2),
+    ];
+
+    for instruction in instructions {
+        let bytes: Vec<u8> = instruction.into();
+        let (decoded, _) = Instruction::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.into_assembly(), instruction.into_assembly());
+    }
+
+    let bytes: Vec<u8> = Instruction::Ldf(Immediate::from(12.22), Register::R0).into();
+
+    assert_eq!(
+        Instruction::from_bytes(&bytes).unwrap().0.into_assembly(),
+        "ldf $r0, 0f12.22"
+    );
+}
@@ -0,0 +1,109 @@
+use vxl_iset::assembler::{assemble, assemble_with};
+use vxl_iset::encoding::InstructionEncoding;
+use vxl_iset::program::Program;
+use vxl_iset::vxl_file::{LoadError, VXLFile, VXLHeader};
+
+/// Sums the integers 1 to 100 into a heap array, then sums the array in a function, guarded by an
+/// exception handler.
+const SUM: &str = "
+    try handler
+    malloci $r1, 0u800
+    ldi $r0, 0i0
+    ldi $r2, 0i1
+    ldi $r3, 0i100
+fill:
+    addi $r0, $r0, $r2
+    iseti $r1, 0u8, $r0
+    ldi $r4, 0i1
+    addi $r2, $r2, $r4
+    cmpi $r2, $r3
+    jle fill
+    call sum
+    endtry
+    halt
+sum:
+    enter 0u2
+    ldi $r0, 0i0
+    ldi $r2, 0i0
+    ldi $r5, 0i800
+loop:
+    geti $r4, $r1, $r2
+    addi $r0, $r0, $r4
+    ldi $r4, 0i8
+    addi $r2, $r2, $r4
+    stfp $r0, 0i-1
+    cmpi $r2, $r5
+    jlt loop
+    leave
+    ret
+handler:
+    throwi 0u1
+";
+
+/// A recursive fibonacci.
+const FIB: &str = "
+    ldi $r0, 0i20
+    call fib
+    halt
+fib:
+    ldi $r1, 0i2
+    cmpi $r0, $r1
+    jlt done
+    push $r0
+    ldi $r1, 0i-1
+    addi $r0, $r0, $r1
+    call fib
+    pop $r1
+    push $r0
+    ldi $r2, 0i-2
+    addi $r0, $r1, $r2
+    call fib
+    pop $r1
+    addi $r0, $r0, $r1
+done:
+    ret
+";
+
+#[test]
+fn compact_programs_are_smaller() {
+    for (source, fixed_len, compact_len) in [(SUM, 169, 74), (FIB, 73, 45)] {
+        let fixed = assemble(source).unwrap();
+        let compact = assemble_with(source, InstructionEncoding::Compact).unwrap();
+
+        assert_eq!((fixed.len(), compact.len()), (fixed_len, compact_len));
+        let fixed = Program::predecode(&fixed).unwrap();
+        let compact = Program::predecode_with(&compact, InstructionEncoding::Compact).unwrap();
+
+        // Addresses and displacements differ between the layouts but every branch keeps its target.
+        assert_eq!(fixed.len(), compact.len());
+
+        for index in 0..fixed.len() {
+            assert_eq!(
+                fixed.instructions()[index].opcode(),
+                compact.instructions()[index].opcode()
+            );
+            assert_eq!(fixed.target(index), compact.target(index));
+        }
+    }
+}
+
+#[test]
+fn version_selects_encoding() {
+    let compact = assemble_with(FIB, InstructionEncoding::Compact).unwrap();
+    let file = |version: u8, contents: &[u8]| {
+        VXLFile::new(
+            VXLHeader::new(version, contents.len() as u64, 0, 0, [0; 28]),
+            contents.to_vec(),
+        )
+    };
+
+    assert_eq!(
+        file(1, &compact).program(),
+        Ok(Program::predecode_with(&compact, InstructionEncoding::Compact).unwrap())
+    );
+    assert!(file(0, &compact).program().is_err());
+    assert_eq!(
+        file(3, &compact).program(),
+        Err(LoadError::UnsupportedVersion(3))
+    );
+}
@@ -0,0 +1,173 @@
+use vxl_iset::assembler::{assemble_object, AssembleErrorKind};
+use vxl_iset::debug_info::LineTable;
+use vxl_iset::instruction::Instruction;
+use vxl_iset::instruction_arguments::Address;
+use vxl_iset::linker::{link, LinkError};
+use vxl_iset::object::{Object, ObjectError, Relocation};
+use vxl_iset::symbol::{Symbol, SymbolKind, SymbolTable};
+
+const MAIN: &str = "
+.loc \"main.vl\" 1
+    try handler
+    ldi $r0, 0i7
+    call square
+    call cube
+    endtry
+    halt
+handler:
+    ret
+";
+
+const MATH: &str = "
+.global square
+.global cube
+.loc \"math.vl\" 1
+square:
+    muli $r0, $r0, $r0
+    ret
+cube:
+    mov $r1, $r0
+    call square
+    muli $r0, $r0, $r1
+loop:
+    jne loop
+    ret
+";
+
+#[test]
+fn object_round_trip() {
+    let object = assemble_object(MAIN).unwrap();
+    let bytes: Vec<u8> = (&object).into();
+
+    assert_eq!(
+        object.undefined_symbols().into_iter().collect::<Vec<_>>(),
+        ["cube", "square"]
+    );
+    assert_eq!(object.relocations().len(), 3);
+    assert_eq!(Object::from_bytes(&bytes), Ok(object));
+    assert_eq!(
+        Object::from_bytes(&bytes[1..]),
+        Err(ObjectError::InvalidMagic)
+    );
+    assert_eq!(Relocation::new(0, 0, &"a".repeat(256)), None);
+    assert_eq!(
+        assemble_object(&format!("call {}", "a".repeat(300)))
+            .unwrap_err()
+            .kind(),
+        &AssembleErrorKind::InvalidOperand("a".repeat(300))
+    );
+}
+
+#[test]
+fn link_objects() {
+    let main = assemble_object(MAIN).unwrap();
+    let math = assemble_object(MATH).unwrap();
+    let file = link(&[main.clone(), math]).unwrap();
+    let program = file.program().unwrap();
+    let symbols = file.symbols().unwrap();
+    let lines = file.line_table().unwrap();
+    let square = symbols.symbol_by_name("square").unwrap();
+    let cube = symbols.symbol_by_name("cube").unwrap();
+    let handler = symbols.symbol_by_name("handler").unwrap();
+
+    assert_eq!(square.offset(), main.code().len() as u64);
+    assert_eq!(cube.kind(), SymbolKind::Function);
+    assert_eq!(
+        program.get(0),
+        Some(Instruction::Try(Address::code(handler.offset())))
+    );
+    assert_eq!(
+        program.get(2),
+        Some(Instruction::Call(Address::code(square.offset())))
+    );
+    assert_eq!(
+        program.get(3),
+        Some(Instruction::Call(Address::code(cube.offset())))
+    );
+    assert_eq!(
+        program.get(program.index_of(handler.offset()).unwrap()),
+        Some(Instruction::Ret)
+    );
+    assert_eq!(
+        program.target(program.index_of(cube.offset()).unwrap() + 1),
+        program.index_of(square.offset())
+    );
+    assert_eq!(lines.location(cube.offset()).unwrap().file, "math.vl");
+    assert_eq!(lines.location(0).unwrap().file, "main.vl");
+}
+
+#[test]
+fn link_errors() {
+    let main = assemble_object(MAIN).unwrap();
+    let math = assemble_object(MATH).unwrap();
+
+    assert_eq!(
+        link(&[main]).unwrap_err(),
+        LinkError::UndefinedSymbol("square".to_string())
+    );
+    assert_eq!(
+        link(&[math.clone(), math.clone()]).unwrap_err(),
+        LinkError::DuplicateSymbol("square".to_string())
+    );
+    assert!(assemble_object("jne16 elsewhere").is_err());
+    assert!(assemble_object(".global elsewhere").is_err());
+
+    let heap = Object::new(
+        Instruction::Freea(Address::heap(0)).into(),
+        SymbolTable::default(),
+        vec![Relocation::new(0, 0, "cube").unwrap()],
+        LineTable::new(),
+    );
+
+    let data = Object::new(
+        Instruction::Call(Address::code(0)).into(),
+        SymbolTable::new(vec![
+            Symbol::new("table", SymbolKind::Object, 1, 0, 8).unwrap()
+        ]),
+        vec![Relocation::new(0, 0, "table").unwrap()],
+        LineTable::new(),
+    );
+
+    assert_eq!(
+        link(&[data]).unwrap_err(),
+        LinkError::InvalidRelocation {
+            object: 0,
+            offset: 0
+        }
+    );
+    assert_eq!(
+        link(&[heap, math]).unwrap_err(),
+        LinkError::InvalidRelocation {
+            object: 0,
+            offset: 0
+        }
+    );
+}
+
+#[test]
+fn link_local_symbols() {
+    let main = assemble_object("start: call helper\ncall lib\nhalt\nhelper: ret\n").unwrap();
+    let lib = assemble_object(".global lib\nlib: call helper\nret\nhelper: ret\n").unwrap();
+    let file = link(&[main.clone(), lib]).unwrap();
+    let program = file.program().unwrap();
+    let symbols = file.symbols().unwrap();
+    let helpers = symbols
+        .symbols()
+        .iter()
+        .filter(|symbol| symbol.name() == "helper")
+        .map(|symbol| symbol.offset())
+        .collect::<Vec<_>>();
+    let lib = symbols.symbol_by_name("lib").unwrap();
+
+    assert!(lib.is_global());
+    assert_eq!(helpers.len(), 2);
+    assert_eq!(program.target(0), program.index_of(helpers[0]));
+    assert_eq!(
+        program.target(program.index_of(lib.offset()).unwrap()),
+        program.index_of(helpers[1])
+    );
+    assert_eq!(
+        link(&[assemble_object("call helper").unwrap(), main]).unwrap_err(),
+        LinkError::UndefinedSymbol("helper".to_string())
+    );
+}
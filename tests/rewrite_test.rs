@@ -0,0 +1,67 @@
+use vxl_iset::instruction::{Instruction, InstructionVisitor};
+use vxl_iset::instruction_arguments::{Address, Condition, Immediate, Operand, Register};
+
+#[test]
+fn operands_in_assembly_order() {
+    let instruction = Instruction::Copyi(
+        Immediate::from(0u64),
+        Immediate::from(1u64),
+        Immediate::from(2u64),
+        Register::R0,
+        Register::R1,
+    );
+
+    let operands: Vec<Operand> = instruction.operands().collect();
+
+    assert_eq!(
+        operands,
+        vec![
+            Operand::Register(Register::R0),
+            Operand::Immediate(Immediate::from(0u64)),
+            Operand::Register(Register::R1),
+            Operand::Immediate(Immediate::from(1u64)),
+            Operand::Immediate(Immediate::from(2u64)),
+        ]
+    );
+    assert_eq!(Instruction::Nop.operands().count(), 0);
+}
+
+#[test]
+fn map_operands() {
+    let instruction = Instruction::Csel(Condition::LT, Register::R0, Register::R1, Register::R2);
+
+    assert_eq!(
+        instruction.map_registers(|register| Register::from(register as u8 + 1)),
+        Instruction::Csel(Condition::LT, Register::R1, Register::R2, Register::R3)
+    );
+    assert_eq!(instruction.map_addresses(|_| unreachable!()), instruction);
+
+    let relocated = Instruction::Jmp(Address::from(5u64))
+        .map_addresses(|address| Address::from(Into::<u64>::into(address) + 0x100));
+
+    assert_eq!(relocated, Instruction::Jmp(Address::from(0x105u64)));
+}
+
+#[test]
+fn visitor_falls_back_to_default() {
+    struct Jumps(Vec<Address>);
+
+    impl InstructionVisitor for Jumps {
+        type Output = bool;
+
+        fn visit_default(&mut self, _instruction: Instruction) -> bool {
+            false
+        }
+
+        fn visit_jmp(&mut self, a: Address) -> bool {
+            self.0.push(a);
+            true
+        }
+    }
+
+    let mut jumps = Jumps(Vec::new());
+
+    assert!(jumps.visit(Instruction::Jmp(Address::from(3u64))));
+    assert!(!jumps.visit(Instruction::Nop));
+    assert_eq!(jumps.0, vec![Address::from(3u64)]);
+}